@@ -48,6 +48,7 @@ fn bench_cache_cold_vs_hot(c: &mut Criterion) {
             normal: Default::default(),
             roughness: Default::default(),
             emissive: None,
+            height: None,
         }),
         false,
         None,
@@ -0,0 +1,446 @@
+//! Texture arrays for terrain splatting.
+//!
+//! Splat shaders sample several surface textures (ground, rock, sand, snow)
+//! from one `texture_2d_array` per map instead of binding each layer
+//! separately.  [`PendingTextureArray`] generates every layer on the
+//! texture-generation pool and [`poll_texture_array_tasks`] assembles the
+//! finished layers into three array images — albedo, normal and ORM, plus
+//! emissive when any layer glows — delivered as [`TextureArrayReady`]:
+//!
+//! ```rust,ignore
+//! let layers = [
+//!     TextureConfig::Ground(GroundConfig::default()),
+//!     TextureConfig::Rock(RockConfig::default()),
+//!     TextureConfig::Sand(SandConfig::default()),
+//!     TextureConfig::Snow(SnowConfig::default()),
+//! ];
+//! commands.spawn(PendingTextureArray::new(&layers, 1024, 1024).unwrap());
+//!
+//! fn on_ready(arrays: Query<&TextureArrayReady, Added<TextureArrayReady>>) {
+//!     for ready in &arrays {
+//!         // ready.0.albedo is a 4-layer `texture_2d_array`.
+//!     }
+//! }
+//! ```
+//!
+//! Every layer keeps its own full mip chain (computed on the worker like the
+//! single-texture path) and the images use a repeat sampler, since splat
+//! layers tile across the terrain.  Card configs may be used as layers but
+//! are sampled with repeat as well.
+
+use std::sync::mpsc;
+
+use bevy::{
+    asset::{Assets, Handle, RenderAssetUsages},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{Commands, Query, ResMut},
+    },
+    image::{Image, ImageAddressMode},
+    render::render_resource::{
+        Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
+};
+
+use crate::async_gen::{
+    PendingTexture, TextureFailed, TextureFailure, TexturePriority, UploadBudget, map_upload_bytes,
+    take_admission,
+};
+use crate::generator::{TextureMap, trilinear_sampler};
+use crate::material::TextureConfig;
+
+/// Handles to the array images built by [`poll_texture_array_tasks`] or
+/// [`maps_to_array_images`].
+///
+/// Every image has `layer_count` layers in the order the configs were
+/// given and a [`TextureViewDimension::D2Array`] view, so it binds as a
+/// `texture_2d_array` even with a single layer.
+#[derive(Clone)]
+pub struct TextureArrayHandles {
+    /// sRGB albedo array.
+    pub albedo: Handle<Image>,
+    /// Linear tangent-space normal array.
+    pub normal: Handle<Image>,
+    /// Linear ORM (Occlusion/Roughness/Metallic) array.
+    pub roughness: Handle<Image>,
+    /// sRGB emissive array, when at least one layer produced a glow map;
+    /// layers without one are black.
+    pub emissive: Option<Handle<Image>>,
+    /// Number of array layers.
+    pub layer_count: u32,
+}
+
+/// Added to the entity by [`poll_texture_array_tasks`] once every layer has
+/// been generated and the array images are uploaded.
+#[derive(Component)]
+pub struct TextureArrayReady(pub TextureArrayHandles);
+
+/// Spawned onto an entity to request a background-generated texture array.
+///
+/// Each layer runs as its own [`PendingTexture`] task, so layers generate
+/// in parallel up to [`AsyncTextureConfig::pool_threads`].  Dropping the
+/// component cancels every layer that has not started yet.
+///
+/// [`AsyncTextureConfig::pool_threads`]: crate::AsyncTextureConfig::pool_threads
+#[derive(Component)]
+pub struct PendingTextureArray {
+    layers: Vec<PendingTexture>,
+    /// Finished layers, filled in as their tasks complete.
+    maps: Vec<Option<TextureMap>>,
+    usages: RenderAssetUsages,
+}
+
+impl PendingTextureArray {
+    /// Start generating one layer per entry of `configs`, all at
+    /// `width × height` texels.
+    ///
+    /// Returns `None` when `configs` is empty or contains
+    /// [`TextureConfig::None`], which has nothing to generate.
+    pub fn new(configs: &[TextureConfig], width: u32, height: u32) -> Option<Self> {
+        if configs.is_empty() {
+            return None;
+        }
+        let layers = configs
+            .iter()
+            .map(|config| config.spawn(width, height))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            maps: layers.iter().map(|_| None).collect(),
+            layers,
+            usages: RenderAssetUsages::RENDER_WORLD,
+        })
+    }
+
+    /// Schedule every layer at `priority`; see
+    /// [`PendingTexture::with_priority`].
+    pub fn with_priority(self, priority: TexturePriority) -> Self {
+        for layer in &self.layers {
+            layer.set_priority(priority);
+        }
+        self
+    }
+
+    /// Upload the arrays with `usages` instead of the default
+    /// [`RenderAssetUsages::RENDER_WORLD`].  Include
+    /// [`RenderAssetUsages::MAIN_WORLD`] to keep the pixels CPU-resident,
+    /// e.g. to patch single layers later.
+    pub fn with_usages(mut self, usages: RenderAssetUsages) -> Self {
+        self.usages = usages;
+        self
+    }
+
+    /// Stop every layer still generating; see [`PendingTexture::cancel`].
+    /// The array is then reported as [`TextureFailed`] with
+    /// [`TextureFailure::Cancelled`].
+    pub fn cancel(&self) {
+        for layer in &self.layers {
+            layer.cancel();
+        }
+    }
+
+    /// Number of layers requested.
+    pub fn layer_count(&self) -> u32 {
+        self.layers.len() as u32
+    }
+
+    /// Highest layer priority and total upload size, once every layer has
+    /// arrived — collected or still parked in its task; `None` before.
+    pub(crate) fn ready_upload(&self) -> Option<(TexturePriority, u64)> {
+        let mut bytes = 0;
+        for (layer, map) in self.layers.iter().zip(&self.maps) {
+            bytes += match map {
+                Some(map) => map_upload_bytes(map),
+                None => layer.ready_upload_bytes()?,
+            };
+        }
+        let priority = self.layers.iter().map(PendingTexture::priority).max()?;
+        Some((priority, bytes))
+    }
+}
+
+/// Build array images from already generated maps, one layer per map in
+/// order.
+///
+/// Maps without a mip chain get one computed first
+/// ([`TextureMap::with_mips`]).
+///
+/// # Panics
+///
+/// Panics if `maps` is empty or the maps differ in size.
+pub fn maps_to_array_images(
+    maps: Vec<TextureMap>,
+    usages: RenderAssetUsages,
+    images: &mut Assets<Image>,
+) -> TextureArrayHandles {
+    assert!(!maps.is_empty(), "a texture array needs at least one layer");
+    let (width, height) = (maps[0].width, maps[0].height);
+    assert!(
+        maps.iter().all(|m| m.width == width && m.height == height),
+        "texture array layers must share one resolution"
+    );
+    let maps: Vec<TextureMap> = maps.into_iter().map(TextureMap::with_mips).collect();
+    let mip_level_count = maps[0].mip_level_count;
+    let layer_count = maps.len() as u32;
+
+    let any_emissive = maps.iter().any(|m| m.emissive.is_some());
+    let mut albedo = Vec::with_capacity(maps[0].albedo.len() * maps.len());
+    let mut normal = Vec::with_capacity(albedo.capacity());
+    let mut roughness = Vec::with_capacity(albedo.capacity());
+    let mut emissive = Vec::with_capacity(if any_emissive { albedo.capacity() } else { 0 });
+    // Layer-major: every mip of layer 0, then every mip of layer 1, … —
+    // exactly the base-plus-mips buffers laid end to end.
+    for map in maps {
+        if any_emissive {
+            match &map.emissive {
+                Some(layer) => emissive.extend_from_slice(layer),
+                None => emissive
+                    .extend(std::iter::repeat_n([0, 0, 0, 255], map.albedo.len() / 4).flatten()),
+            }
+        }
+        albedo.extend_from_slice(&map.albedo);
+        normal.extend_from_slice(&map.normal);
+        roughness.extend_from_slice(&map.roughness);
+    }
+
+    let make = |data, format| {
+        array_image(
+            data,
+            width,
+            height,
+            layer_count,
+            mip_level_count,
+            format,
+            usages,
+        )
+    };
+    TextureArrayHandles {
+        albedo: images.add(make(albedo, TextureFormat::Rgba8UnormSrgb)),
+        normal: images.add(make(normal, TextureFormat::Rgba8Unorm)),
+        roughness: images.add(make(roughness, TextureFormat::Rgba8Unorm)),
+        emissive: any_emissive.then(|| images.add(make(emissive, TextureFormat::Rgba8UnormSrgb))),
+        layer_count,
+    }
+}
+
+fn array_image(
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    layer_count: u32,
+    mip_level_count: u32,
+    format: TextureFormat,
+    usages: RenderAssetUsages,
+) -> Image {
+    let mut image = Image::new_uninit(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: layer_count,
+        },
+        TextureDimension::D2,
+        format,
+        usages,
+    );
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image.data = Some(data);
+    image.sampler = trilinear_sampler(ImageAddressMode::Repeat);
+    // Without an explicit view a single-layer array would bind as a plain
+    // `texture_2d`.
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::D2Array),
+        ..Default::default()
+    });
+    image
+}
+
+/// Bevy system — collects finished layers of every [`PendingTextureArray`]
+/// and, once all have arrived, uploads the arrays and swaps the component
+/// for [`TextureArrayReady`].
+///
+/// The finished arrays count against the [`UploadBudget`] as one upload,
+/// admitted by
+/// [`plan_texture_uploads`](crate::async_gen::plan_texture_uploads) like
+/// any other.
+/// A layer that fails, panics or is cancelled abandons the whole array:
+/// the error is logged and the pending component replaced by a
+/// [`TextureFailed`],
+/// mirroring [`poll_texture_tasks`](crate::async_gen::poll_texture_tasks).
+pub fn poll_texture_array_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut PendingTextureArray)>,
+    mut images: ResMut<Assets<Image>>,
+    mut budget: Option<ResMut<UploadBudget>>,
+) {
+    'arrays: for (entity, mut pending) in &mut tasks {
+        let pending = &mut *pending;
+        for (layer, slot) in pending.layers.iter().zip(pending.maps.iter_mut()) {
+            if slot.is_some() {
+                continue;
+            }
+            // Layers are only assembled, not uploaded, here; the budget
+            // admits the whole array below.
+            match layer.try_take(true) {
+                Ok(Ok(generated)) => *slot = Some(generated.map),
+                Ok(Err(failure)) => {
+                    if !matches!(failure, TextureFailure::Cancelled) {
+                        bevy::log::error!("Texture array layer: {failure}");
+                    }
+                    commands
+                        .entity(entity)
+                        .remove::<PendingTextureArray>()
+                        .insert(TextureFailed(failure));
+                    continue 'arrays;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    bevy::log::error!("Texture array layer: {}", TextureFailure::Lost);
+                    commands
+                        .entity(entity)
+                        .remove::<PendingTextureArray>()
+                        .insert(TextureFailed(TextureFailure::Lost));
+                    continue 'arrays;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+        }
+
+        if pending.maps.iter().all(Option::is_some) && take_admission(budget.as_deref_mut(), entity)
+        {
+            let maps = pending.maps.iter_mut().filter_map(Option::take).collect();
+            let handles = maps_to_array_images(maps, pending.usages, &mut images);
+            commands
+                .entity(entity)
+                .remove::<PendingTextureArray>()
+                .insert(TextureArrayReady(handles));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::ecs::schedule::Schedule;
+    use bevy::ecs::world::World;
+
+    use crate::generator::TextureGenerator;
+    use crate::ground::{GroundConfig, GroundGenerator};
+    use crate::lava::{LavaConfig, LavaGenerator};
+    use crate::rock::RockConfig;
+
+    #[test]
+    fn builds_layer_major_arrays_with_mips() {
+        let ground = GroundGenerator::new(GroundConfig::default())
+            .generate(8, 8)
+            .expect("ground");
+        let lava = LavaGenerator::new(LavaConfig::default())
+            .generate(8, 8)
+            .expect("lava")
+            .with_mips();
+        let ground_chain = TextureMap {
+            albedo: ground.albedo.clone(),
+            normal: ground.normal.clone(),
+            roughness: ground.roughness.clone(),
+            emissive: None,
+            width: 8,
+            height: 8,
+            mip_level_count: 1,
+        }
+        .with_mips();
+        let lava_albedo = lava.albedo.clone();
+        let lava_emissive = lava.emissive.clone().expect("lava glows");
+
+        let mut images = Assets::<Image>::default();
+        let handles = maps_to_array_images(
+            vec![ground, lava],
+            RenderAssetUsages::MAIN_WORLD,
+            &mut images,
+        );
+        assert_eq!(handles.layer_count, 2);
+
+        let albedo = images.get(&handles.albedo).expect("albedo array");
+        let size = albedo.texture_descriptor.size;
+        assert_eq!(
+            (size.width, size.height, size.depth_or_array_layers),
+            (8, 8, 2)
+        );
+        assert_eq!(albedo.texture_descriptor.mip_level_count, 4);
+        assert_eq!(
+            albedo.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(
+            albedo
+                .texture_view_descriptor
+                .as_ref()
+                .and_then(|v| v.dimension),
+            Some(TextureViewDimension::D2Array)
+        );
+        let data = albedo.data.as_deref().expect("resident");
+        let layer_len = ground_chain.albedo.len();
+        assert_eq!(&data[..layer_len], ground_chain.albedo.as_slice());
+        assert_eq!(&data[layer_len..], lava_albedo.as_slice());
+
+        // Ground has no glow map: its emissive layer is black.
+        let emissive = images
+            .get(handles.emissive.as_ref().expect("lava adds emissive"))
+            .and_then(|image| image.data.as_deref())
+            .expect("resident");
+        assert!(emissive[..layer_len].chunks(4).all(|p| p == [0, 0, 0, 255]));
+        assert_eq!(&emissive[layer_len..], lava_emissive.as_slice());
+    }
+
+    #[test]
+    fn rejects_empty_and_none_layers() {
+        assert!(PendingTextureArray::new(&[], 8, 8).is_none());
+        assert!(
+            PendingTextureArray::new(
+                &[
+                    TextureConfig::Rock(RockConfig::default()),
+                    TextureConfig::None
+                ],
+                8,
+                8
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn poll_system_delivers_texture_array_ready() {
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        let pending = PendingTextureArray::new(
+            &[
+                TextureConfig::Ground(GroundConfig::default()),
+                TextureConfig::Rock(RockConfig::default()),
+            ],
+            8,
+            8,
+        )
+        .expect("two layers");
+        assert_eq!(pending.layer_count(), 2);
+        let entity = world.spawn(pending).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(poll_texture_array_tasks);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while world.get::<TextureArrayReady>(entity).is_none() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "texture array timed out"
+            );
+            schedule.run(&mut world);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert!(world.get::<PendingTextureArray>(entity).is_none());
+        let ready = world.get::<TextureArrayReady>(entity).expect("ready");
+        assert_eq!(ready.0.layer_count, 2);
+        assert!(ready.0.emissive.is_none());
+        let images = world.resource::<Assets<Image>>();
+        let normal = images.get(&ready.0.normal).expect("normal array");
+        assert_eq!(normal.texture_descriptor.size.depth_or_array_layers, 2);
+        assert_eq!(normal.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+    }
+}
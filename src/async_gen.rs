@@ -23,6 +23,22 @@
 //!
 //! // Later, query for TextureReady to consume the handles.
 //! ```
+//!
+//! # Scheduling
+//!
+//! Queued tasks start in [`TexturePriority`] order — raise a texture that
+//! just became visible with [`PendingTexture::set_priority`] and it jumps
+//! every prefetch still waiting for a worker.  Finished maps are uploaded
+//! highest priority first, within the per-frame byte budget set by
+//! [`AsyncTextureConfig::upload_budget_bytes`] (tracked in
+//! [`UploadBudget`]); the rest wait for the next frame.
+//!
+//! One budget pass covers every kind of upload: [`plan_texture_uploads`]
+//! ranks all finished textures, texture arrays and material patches
+//! together and admits what fits, then the polling systems upload exactly
+//! the admitted entities.  The plugin orders the two through
+//! [`TextureUploadSystems`]; apps scheduling the systems themselves must
+//! keep the planner before the pollers.
 
 /// Default concurrency cap applied when no explicit
 /// [`AsyncTextureConfig::pool_threads`] is supplied.
@@ -51,16 +67,242 @@ pub struct AsyncTextureConfig {
     ///
     /// Defaults to [`DEFAULT_POOL_THREADS`].
     pub pool_threads: usize,
+    /// Bytes of finished texture data the polling systems may upload per
+    /// frame; maps beyond it wait for a later frame, so a burst of
+    /// completions spreads out instead of spiking one frame.  The first
+    /// upload of a frame always goes through, however large.
+    ///
+    /// `None` (the default) uploads everything as soon as it finishes.
+    /// Unlike [`pool_threads`](AsyncTextureConfig::pool_threads) this is read
+    /// at plugin build into the [`UploadBudget`] resource, which may be
+    /// changed at runtime.
+    pub upload_budget_bytes: Option<u64>,
 }
 
 impl Default for AsyncTextureConfig {
     fn default() -> Self {
         Self {
             pool_threads: DEFAULT_POOL_THREADS,
+            upload_budget_bytes: None,
+        }
+    }
+}
+
+/// Per-frame allowance for uploading finished texture data, shared by every
+/// polling system.  Inserted by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) from
+/// [`AsyncTextureConfig::upload_budget_bytes`] and refilled each frame by
+/// [`reset_upload_budget`].  Without this resource the polling systems
+/// upload without limit.
+///
+/// With a limit set, [`plan_texture_uploads`] decides each frame which
+/// finished entities upload; the polling systems hold back everything
+/// else.
+#[derive(bevy::ecs::resource::Resource, Clone, Debug, Default)]
+pub struct UploadBudget {
+    /// Bytes allowed per frame; `None` for no limit.
+    pub limit: Option<u64>,
+    spent: u64,
+    /// Entities [`plan_texture_uploads`] admitted this frame and whose
+    /// poller has not uploaded them yet.
+    admitted: EntityHashSet,
+}
+
+impl UploadBudget {
+    /// A fresh budget allowing `limit` bytes per frame.
+    pub fn new(limit: Option<u64>) -> Self {
+        Self {
+            limit,
+            spent: 0,
+            admitted: EntityHashSet::default(),
+        }
+    }
+
+    /// Bytes uploaded so far this frame.
+    pub fn spent(&self) -> u64 {
+        self.spent
+    }
+
+    /// Charge `bytes` if they fit in what is left of this frame's budget.
+    /// The first charge of a frame always succeeds, so a texture larger
+    /// than the whole budget still uploads — alone — instead of never.
+    pub fn try_spend(&mut self, bytes: u64) -> bool {
+        let fits = match self.limit {
+            None => true,
+            Some(limit) => self.spent == 0 || self.spent + bytes <= limit,
+        };
+        if fits {
+            self.spent += bytes;
+        }
+        fits
+    }
+}
+
+/// Whether the poller handling `entity` may upload it this frame: always
+/// without a budget or limit, otherwise only once [`plan_texture_uploads`]
+/// admitted it.  Consumes the admission.
+pub(crate) fn take_admission(budget: Option<&mut UploadBudget>, entity: Entity) -> bool {
+    match budget {
+        Some(budget) if budget.limit.is_some() => budget.admitted.remove(&entity),
+        _ => true,
+    }
+}
+
+/// Bevy system — refills the [`UploadBudget`] at the start of each frame.
+/// Registered in `First` by [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin).
+pub fn reset_upload_budget(mut budget: ResMut<UploadBudget>) {
+    budget.spent = 0;
+    budget.admitted.clear();
+}
+
+/// System sets ordering the upload pass within `Update`: [`Plan`] runs
+/// [`plan_texture_uploads`] (and [`update_generation_progress`]), then
+/// [`Upload`] runs every polling system —
+/// [`poll_texture_tasks`],
+/// [`poll_texture_array_tasks`](crate::array::poll_texture_array_tasks) and
+/// each [`patch_procedural_material_textures`](crate::material::patch_procedural_material_textures).
+///
+/// [`Plan`]: TextureUploadSystems::Plan
+/// [`Upload`]: TextureUploadSystems::Upload
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextureUploadSystems {
+    /// Decide which finished entities fit in this frame's [`UploadBudget`].
+    Plan,
+    /// Upload the admitted entities.
+    Upload,
+}
+
+/// Bevy system — ranks every finished [`PendingTexture`] (material patches
+/// included) and [`PendingTextureArray`] by [`TexturePriority`] and admits
+/// them into this frame's [`UploadBudget`] until it runs out, so priority
+/// holds across all polling systems rather than within each.  Equal
+/// priorities keep query order, textures before arrays.
+///
+/// An array ranks at the highest priority among its layers and is charged
+/// as one upload once every layer has arrived.  Does nothing without a
+/// budget limit.
+pub fn plan_texture_uploads(
+    budget: Option<ResMut<UploadBudget>>,
+    tasks: Query<(Entity, &PendingTexture)>,
+    arrays: Query<(Entity, &PendingTextureArray)>,
+) {
+    let Some(mut budget) = budget else {
+        return;
+    };
+    if budget.limit.is_none() {
+        return;
+    }
+    let mut ready: Vec<(TexturePriority, Entity, u64)> = tasks
+        .iter()
+        .filter_map(|(entity, pending)| {
+            Some((pending.priority(), entity, pending.ready_upload_bytes()?))
+        })
+        .chain(arrays.iter().filter_map(|(entity, pending)| {
+            let (priority, bytes) = pending.ready_upload()?;
+            Some((priority, entity, bytes))
+        }))
+        .collect();
+    ready.sort_by_key(|&(priority, ..)| std::cmp::Reverse(priority));
+    for (_, entity, bytes) in ready {
+        if budget.try_spend(bytes) {
+            budget.admitted.insert(entity);
         }
     }
 }
 
+/// Scheduling priority of a [`PendingTexture`].
+///
+/// Orders both the start of queued tasks on the generation pool and the
+/// uploads of finished ones.  Equal priorities keep submission order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum TexturePriority {
+    /// Speculative work (upcoming areas, warm-up) that yields to everything
+    /// else.
+    Prefetch = 0,
+    /// The default.
+    #[default]
+    Normal = 1,
+    /// Needed for something on screen now.
+    Visible = 2,
+}
+
+impl TexturePriority {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Prefetch,
+            1 => Self::Normal,
+            _ => Self::Visible,
+        }
+    }
+}
+
+/// A task waiting for a pool worker; see [`JobQueue`].
+struct QueuedJob {
+    /// Shared with the [`PendingTexture`], so reprioritising reorders the
+    /// job while it waits.
+    priority: Arc<AtomicU8>,
+    seq: u64,
+    run: Box<dyn FnOnce() + Send>,
+}
+
+/// Tasks waiting for a pool worker.  Every submission pushes one job and
+/// spawns one runner onto the pool; each runner, when it starts, takes the
+/// highest-priority job still waiting — so rayon's FIFO order decides only
+/// *when* a worker frees up, not *which* task it runs.
+struct JobQueue {
+    jobs: Vec<QueuedJob>,
+    next_seq: u64,
+}
+
+impl JobQueue {
+    const fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    fn push(&mut self, priority: Arc<AtomicU8>, run: Box<dyn FnOnce() + Send>) {
+        self.jobs.push(QueuedJob {
+            priority,
+            seq: self.next_seq,
+            run,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Remove the highest-priority job, oldest first among equals.  A
+    /// linear scan: the queue is at most a few hundred entries, and
+    /// priorities change while jobs wait, which a heap would not notice.
+    fn pop(&mut self) -> Option<Box<dyn FnOnce() + Send>> {
+        let next = self
+            .jobs
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, job)| {
+                (
+                    job.priority.load(Ordering::Relaxed),
+                    std::cmp::Reverse(job.seq),
+                )
+            })
+            .map(|(i, _)| i)?;
+        Some(self.jobs.swap_remove(next).run)
+    }
+}
+
+static JOB_QUEUE: std::sync::Mutex<JobQueue> = std::sync::Mutex::new(JobQueue::new());
+
+/// Pool-side half of a [`JobQueue`] submission: run whichever job is most
+/// urgent now.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_next_job() {
+    let job = JOB_QUEUE.lock().unwrap_or_else(|e| e.into_inner()).pop();
+    if let Some(job) = job {
+        job();
+    }
+}
+
 /// Resolves the requested thread-count to an actual count.
 fn resolve_pool_threads(cfg: &AsyncTextureConfig) -> usize {
     if cfg.pool_threads == 0 {
@@ -121,6 +363,15 @@ fn build_pool(cfg: &AsyncTextureConfig) -> Option<rayon::ThreadPool> {
         // larger worker stack rather than an algorithm change.
         .stack_size(8 * 1024 * 1024)
         .thread_name(|i| format!("texture-gen-{i}"))
+        // Jobs catch their own panics (see `run_task` and `run_on_pool`);
+        // this only keeps a stray one from aborting the process, which is
+        // rayon's default for a panicking `spawn`.
+        .panic_handler(|payload| {
+            bevy::log::error!(
+                "bevy_symbios_texture: texture-gen worker panicked: {}",
+                panic_message(&*payload)
+            );
+        })
         .build()
     {
         Ok(pool) => Some(pool),
@@ -150,25 +401,37 @@ fn gen_pool() -> Option<&'static rayon::ThreadPool> {
     .as_ref()
 }
 
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
 use std::sync::{
     Arc, OnceLock,
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering},
     mpsc,
 };
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 use bevy::{
-    asset::Assets,
+    asset::{Assets, RenderAssetUsages},
     ecs::{
+        change_detection::DetectChangesMut,
         component::Component,
-        entity::Entity,
+        entity::{Entity, EntityHashSet},
+        schedule::SystemSet,
         system::{Commands, Query, ResMut},
     },
-    image::Image,
+    image::{Image, ImageAddressMode},
 };
 
+use crate::array::PendingTextureArray;
+use crate::cache::PixelLoader;
+use crate::cancellable::{CancellableGenerator, GenerationContext};
 use crate::generator::{
     GeneratedHandles, TextureError, TextureGenerator, TextureMap, map_to_images, map_to_images_card,
 };
+use crate::height::HeightMap;
+use crate::normal::BoundaryMode;
 
 /// Spawned onto an entity to request background texture generation.
 ///
@@ -184,34 +447,573 @@ use crate::generator::{
 /// atomic cancellation flag.  Tasks that have not yet started will see the
 /// flag and exit without doing any work, preventing zombie tasks from
 /// saturating the thread pool when entities are rapidly spawned and destroyed.
+/// [`cancel`](PendingTexture::cancel) sets the same flag but keeps the
+/// component, which then reports [`TextureFailure::Cancelled`].
+///
+/// How soon a started task stops depends on its generator.  Tasks built
+/// with [`cancellable`](PendingTexture::cancellable) hand the flag to a
+/// [`CancellableGenerator`], which checks it between rows.  The built-in
+/// generators run through the core [`TextureGenerator::generate`], which
+/// takes no cancellation token: their generator call always runs to
+/// completion, and the flag only stops the stages this crate owns — the
+/// height recovery within one solver sweep, and the mip chain.
+///
+/// Tasks are scheduled by [`TexturePriority`] (default
+/// [`Normal`](TexturePriority::Normal)); see
+/// [`with_priority`](PendingTexture::with_priority).
+///
+/// Spawning one also inserts a [`GenerationProgress`], which
+/// [`update_generation_progress`] refreshes from the worker every frame.
 #[derive(Component)]
+#[require(GenerationProgress)]
 pub struct PendingTexture {
     // Wrapped in Mutex so the struct is Sync, which Bevy's Component bound requires.
-    pub(crate) rx: std::sync::Mutex<mpsc::Receiver<Result<TextureMap, TextureError>>>,
-    /// Set to `true` on drop; the background task checks this before starting.
-    cancelled: Arc<AtomicBool>,
+    pub(crate) rx: std::sync::Mutex<mpsc::Receiver<Result<GeneratedTexture, TextureFailure>>>,
+    /// A result received from the worker but not yet handed over — while
+    /// [`plan_texture_uploads`] sizes it, or until the budget admits it.
+    parked: std::sync::Mutex<Option<Result<GeneratedTexture, TextureFailure>>>,
+    /// Cancellation flag (set on drop) and progress, shared with the worker.
+    control: Arc<TaskControl>,
+    /// [`TexturePriority`] as `u8`, shared with the queued job.
+    priority: Arc<AtomicU8>,
     /// `true` for alpha-masked cards (leaf, twig, window, stained glass,
     /// iron grille) and sprite atlases that need a clamp-to-edge sampler.
     is_card: bool,
+    /// One-off consumer registered with [`on_ready`](PendingTexture::on_ready).
+    ready_callback: std::sync::Mutex<Option<ReadyCallback>>,
 }
 
+/// Callback run by [`poll_texture_tasks`] or the material patch systems once
+/// a task's images are uploaded.
+type ReadyCallback = Box<dyn FnOnce(&GeneratedHandles, &mut Commands) + Send>;
+
 impl PendingTexture {
+    /// Submit `generator` at `width × height`, also recovering its height
+    /// field on the worker when `with_height` is set.
+    pub(crate) fn from_generator<G>(
+        generator: G,
+        width: u32,
+        height: u32,
+        is_card: bool,
+        with_height: bool,
+    ) -> Self
+    where
+        G: TextureGenerator + Send + 'static,
+    {
+        spawn_task(
+            move || generator.generate(width, height),
+            is_card,
+            with_height,
+        )
+    }
+
+    /// Spawn `generator` at `width × height` texels as a task that stops
+    /// between rows once cancelled — see [`cancel`](PendingTexture::cancel)
+    /// and the [`cancellable`](crate::cancellable) module.
+    ///
+    /// [`poll_texture_tasks`] uploads the result with [`map_to_images`],
+    /// giving a repeat-wrapping sampler suitable for tileable surfaces.
+    pub fn cancellable<G>(generator: G, width: u32, height: u32) -> Self
+    where
+        G: CancellableGenerator + Send + 'static,
+    {
+        spawn_cancellable_task(generator, width, height, false, false)
+    }
+
+    /// [`cancellable`](PendingTexture::cancellable) for an alpha-masked
+    /// card, uploaded with [`map_to_images_card`] (clamp-to-edge sampler).
+    pub fn cancellable_card<G>(generator: G, width: u32, height: u32) -> Self
+    where
+        G: CancellableGenerator + Send + 'static,
+    {
+        spawn_cancellable_task(generator, width, height, true, false)
+    }
+
+    /// [`cancellable`](PendingTexture::cancellable) that also uploads a
+    /// height map into [`GeneratedHandles::height`]: the generator's own
+    /// field when it [provides one](GenerationContext::provide_height), as
+    /// [`generate_surface_cancellable`](crate::cancellable::generate_surface_cancellable)
+    /// does, or else a [`HeightMap`] reconstructed from the normal map.
+    pub fn cancellable_with_height<G>(generator: G, width: u32, height: u32) -> Self
+    where
+        G: CancellableGenerator + Send + 'static,
+    {
+        spawn_cancellable_task(generator, width, height, false, true)
+    }
+
+    /// Serve `width × height` from `loader` — a cache backend's deferred
+    /// read, see [`PixelLoader`] — and fall back to running `generator`
+    /// when it yields nothing.  Both happen on the worker, so a disk-cache
+    /// hit costs the main thread only the upload.
+    pub(crate) fn from_cache_or_generator<G>(
+        loader: PixelLoader,
+        generator: G,
+        width: u32,
+        height: u32,
+        is_card: bool,
+        with_height: bool,
+    ) -> Self
+    where
+        G: TextureGenerator + Send + 'static,
+    {
+        spawn_finished_task(
+            move |control| {
+                let cached = loader();
+                let from_cache = cached.is_some();
+                if !from_cache && control.is_cancelled() {
+                    return Err(TextureFailure::Cancelled);
+                }
+                let result = cached.map_or_else(|| generator.generate(width, height), Ok);
+                let finished = GeneratedTexture::finish(
+                    result.map_err(TextureFailure::from),
+                    is_card,
+                    with_height,
+                    None,
+                    control,
+                );
+                finished.map(|mut texture| {
+                    texture.from_cache = from_cache;
+                    texture
+                })
+            },
+            is_card,
+        )
+    }
+
     /// Returns `true` if this task should be uploaded with
     /// [`map_to_images_card`] (clamp-to-edge sampler, alpha-masked card)
     /// rather than the default repeat-tiling [`map_to_images`].
     pub fn is_card(&self) -> bool {
         self.is_card
     }
+
+    /// Builder form of [`set_priority`](PendingTexture::set_priority):
+    ///
+    /// ```rust,ignore
+    /// commands.spawn(
+    ///     PendingTexture::bark(BarkConfig::default(), 512, 512)
+    ///         .with_priority(TexturePriority::Prefetch),
+    /// );
+    /// ```
+    pub fn with_priority(self, priority: TexturePriority) -> Self {
+        self.set_priority(priority);
+        self
+    }
+
+    /// Change the task's priority.  A task still waiting for a worker is
+    /// reordered at once; one already running is unaffected until its
+    /// upload, which also goes in priority order.
+    pub fn set_priority(&self, priority: TexturePriority) {
+        self.priority.store(priority as u8, Ordering::Relaxed);
+    }
+
+    /// Run `f` with the uploaded handles once the task completes — for
+    /// one-off consumers that would otherwise need a system polling for
+    /// [`TextureReady`]:
+    ///
+    /// ```rust,ignore
+    /// commands.spawn(
+    ///     PendingTexture::bark(BarkConfig::default(), 512, 512).on_ready(
+    ///         move |handles, commands| {
+    ///             commands.entity(trunk).insert(TrunkAlbedo(handles.albedo.clone()));
+    ///         },
+    ///     ),
+    /// );
+    /// ```
+    ///
+    /// `f` runs inside [`poll_texture_tasks`] in the frame the entity gains
+    /// its `TextureReady` — or, for a task tagged
+    /// [`PatchMaterialTextures`](crate::material::PatchMaterialTextures),
+    /// inside [`patch_procedural_material_textures`](crate::material::patch_procedural_material_textures)
+    /// just before the handles are written into the material.  It is
+    /// dropped unrun if the task fails or is cancelled.  Replaces any
+    /// callback set earlier.
+    pub fn on_ready(
+        mut self,
+        f: impl FnOnce(&GeneratedHandles, &mut Commands) + Send + 'static,
+    ) -> Self {
+        *self
+            .ready_callback
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner()) = Some(Box::new(f));
+        self
+    }
+
+    /// Run the [`on_ready`](PendingTexture::on_ready) callback, if one is
+    /// set, with the uploaded `handles`.  Runs at most once.
+    pub(crate) fn run_ready_callback(&self, handles: &GeneratedHandles, commands: &mut Commands) {
+        let callback = self
+            .ready_callback
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(callback) = callback {
+            callback(handles, commands);
+        }
+    }
+
+    /// Stop the task.  One still queued never starts; a running one stops
+    /// at its next check — between rows for a
+    /// [`cancellable`](PendingTexture::cancellable) task, after the
+    /// generator call otherwise.  Either way the polling systems then
+    /// report [`TextureFailure::Cancelled`] (see [`TextureFailed`]), unless
+    /// the task had already finished.
+    ///
+    /// Despawning the entity cancels the same way without reporting.
+    pub fn cancel(&self) {
+        self.control.cancel();
+    }
+
+    /// Current scheduling priority.
+    pub fn priority(&self) -> TexturePriority {
+        TexturePriority::from_u8(self.priority.load(Ordering::Relaxed))
+    }
+
+    /// What the worker last reported; see [`GenerationProgress`].
+    pub fn progress(&self) -> GenerationProgress {
+        self.control.progress()
+    }
+
+    /// Upload size of the finished map, if the worker has delivered one;
+    /// the result stays parked for [`try_take`](PendingTexture::try_take).
+    /// `None` while running and for failures, which need no budget.
+    pub(crate) fn ready_upload_bytes(&self) -> Option<u64> {
+        let mut parked = self.parked.lock().unwrap_or_else(|e| e.into_inner());
+        if parked.is_none() {
+            *parked = self
+                .rx
+                .lock()
+                .expect("texture thread poisoned")
+                .try_recv()
+                .ok();
+        }
+        match parked.as_ref()? {
+            Ok(generated) => Some(generated.upload_bytes()),
+            Err(_) => None,
+        }
+    }
+
+    /// Non-blocking poll for the task's result.  A finished map is held
+    /// back and reported as [`Empty`](mpsc::TryRecvError::Empty) unless
+    /// `admitted` — see [`take_admission`]; failures are handed over
+    /// regardless.
+    pub(crate) fn try_take(
+        &self,
+        admitted: bool,
+    ) -> Result<Result<GeneratedTexture, TextureFailure>, mpsc::TryRecvError> {
+        let mut parked = self.parked.lock().unwrap_or_else(|e| e.into_inner());
+        let result = match parked.take() {
+            Some(result) => result,
+            None => self
+                .rx
+                .lock()
+                .expect("texture thread poisoned")
+                .try_recv()?,
+        };
+        if result.is_ok() && !admitted {
+            *parked = Some(result);
+            return Err(mpsc::TryRecvError::Empty);
+        }
+        Ok(result)
+    }
+}
+
+/// What a generation task hands back to the polling systems: the map with
+/// its mip chain, plus the recovered height field when one was requested.
+pub(crate) struct GeneratedTexture {
+    pub(crate) map: TextureMap,
+    pub(crate) height: Option<HeightMap>,
+    /// `true` when the map was read back from a cache backend rather than
+    /// generated, so there is nothing new to persist.
+    pub(crate) from_cache: bool,
+    /// Worker wall time from the start of the task to the finished map.
+    pub(crate) elapsed: Duration,
+}
+
+impl GeneratedTexture {
+    /// Worker-side finishing of a generator result: take `exact_height` or
+    /// recover the height field if asked, then compute the mip chain so the main-thread upload in the
+    /// polling systems is a pure buffer move instead of a box-filter pass.
+    ///
+    /// Fails with [`TextureFailure::Cancelled`] when the task is cancelled
+    /// before or during either step.
+    fn finish(
+        result: Result<TextureMap, TextureFailure>,
+        is_card: bool,
+        with_height: bool,
+        exact_height: Option<HeightMap>,
+        control: &TaskControl,
+    ) -> Result<Self, TextureFailure> {
+        let boundary = if is_card {
+            BoundaryMode::Clamp
+        } else {
+            BoundaryMode::Wrap
+        };
+        let map = result?;
+        let height = if !with_height {
+            None
+        } else if let Some(height) = exact_height {
+            Some(height)
+        } else {
+            let on_sweep = |done| {
+                control.report(GenerationStage::Height, Some(done));
+                !control.is_cancelled()
+            };
+            let height = HeightMap::from_texture_map_with(&map, boundary, &on_sweep);
+            Some(height.ok_or(TextureFailure::Cancelled)?)
+        };
+        if control.is_cancelled() {
+            return Err(TextureFailure::Cancelled);
+        }
+        control.report(GenerationStage::Mips, None);
+        Ok(Self {
+            height,
+            map: map.with_mips(),
+            from_cache: false,
+            elapsed: Duration::ZERO,
+        })
+    }
+
+    /// Pixel bytes [`upload`](GeneratedTexture::upload) hands to
+    /// `Assets<Image>`, mip chains included — what the [`UploadBudget`]
+    /// charges.
+    pub(crate) fn upload_bytes(&self) -> u64 {
+        let height = self.height.as_ref().map_or(0, HeightMap::upload_len);
+        map_upload_bytes(&self.map) + height as u64
+    }
+
+    /// Upload with [`map_to_images_card`] or [`map_to_images`], plus the
+    /// height image under the same sampler.
+    pub(crate) fn upload(self, is_card: bool, images: &mut Assets<Image>) -> GeneratedHandles {
+        let (mut handles, address_mode) = if is_card {
+            (
+                map_to_images_card(self.map, images),
+                ImageAddressMode::ClampToEdge,
+            )
+        } else {
+            (map_to_images(self.map, images), ImageAddressMode::Repeat)
+        };
+        handles.height = self.height.map(|height| {
+            images.add(height.into_image(address_mode, RenderAssetUsages::RENDER_WORLD))
+        });
+        handles
+    }
 }
 
 impl Drop for PendingTexture {
     fn drop(&mut self) {
+        self.control.cancel();
+    }
+}
+
+/// Step of a generation task, as reported by [`GenerationProgress`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum GenerationStage {
+    /// Waiting for a pool worker.
+    #[default]
+    Queued = 0,
+    /// Running the generator — or reading the maps back from a disk cache.
+    /// For the built-in generators this single call also derives the
+    /// normal map.
+    Pixels = 1,
+    /// Deriving the normal map from the sampled heights.  Only
+    /// [`CancellableGenerator`]s report it separately, through
+    /// [`GenerationContext::report`]; the built-in generators derive their
+    /// normals inside [`Pixels`](GenerationStage::Pixels).
+    Normals = 2,
+    /// Recovering the height field (only when one was requested).
+    Height = 3,
+    /// Building the mip chain.
+    Mips = 4,
+    /// Finished; the result is waiting to be uploaded.
+    Done = 5,
+}
+
+impl GenerationStage {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Queued,
+            1 => Self::Pixels,
+            2 => Self::Normals,
+            3 => Self::Height,
+            4 => Self::Mips,
+            _ => Self::Done,
+        }
+    }
+}
+
+/// Progress of the [`PendingTexture`] on the same entity, refreshed each
+/// frame by [`update_generation_progress`] for progress bars and the like.
+///
+/// `fraction` is the share of the current stage completed, in `[0, 1]`,
+/// or `None` while the stage cannot measure it — show an indeterminate bar
+/// then.  The height solve reports sweep by sweep, and a
+/// [`CancellableGenerator`] reports [`Pixels`](GenerationStage::Pixels) row
+/// by row through its [`GenerationContext`] (see
+/// [`generate_surface_cancellable`](crate::cancellable::generate_surface_cancellable)).
+/// The built-in generators' `Pixels` and every task's
+/// [`Mips`](GenerationStage::Mips) are single calls into `symbios-texture`,
+/// which has no progress hook, so they stay `None`.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct GenerationProgress {
+    /// Current step.
+    pub stage: GenerationStage,
+    /// Completed share of `stage`; `None` when it is not measurable.
+    pub fraction: Option<f32>,
+}
+
+/// State a worker shares with its [`PendingTexture`].
+pub(crate) struct TaskControl {
+    /// Set by [`PendingTexture::cancel`] or when the `PendingTexture`
+    /// drops; checked between stages, and between rows by a
+    /// [`CancellableGenerator`].
+    cancelled: AtomicBool,
+    /// [`GenerationStage`] from bit 17, a has-fraction flag in bit 16 and
+    /// the stage fraction scaled to `u16::MAX` in the low 16, so all update
+    /// in one store.
+    progress: AtomicU32,
+}
+
+impl TaskControl {
+    pub(crate) fn new() -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+            progress: AtomicU32::new(0),
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn cancel(&self) {
         self.cancelled.store(true, Ordering::Relaxed);
     }
+
+    /// Publish `fraction` of `stage` as done, or `None` for a stage that
+    /// cannot measure its progress.
+    pub(crate) fn report(&self, stage: GenerationStage, fraction: Option<f32>) {
+        let fraction = fraction.map_or(0, |fraction| {
+            1 << 16 | (fraction.clamp(0.0, 1.0) * u16::MAX as f32).round() as u32
+        });
+        self.progress
+            .store((stage as u32) << 17 | fraction, Ordering::Relaxed);
+    }
+
+    pub(crate) fn progress(&self) -> GenerationProgress {
+        let packed = self.progress.load(Ordering::Relaxed);
+        GenerationProgress {
+            stage: GenerationStage::from_u8((packed >> 17) as u8),
+            fraction: (packed & (1 << 16) != 0).then(|| (packed & 0xFFFF) as f32 / u16::MAX as f32),
+        }
+    }
+}
+
+/// Bevy system — copies every [`PendingTexture`]'s worker-side progress
+/// into its [`GenerationProgress`] component, touching change detection
+/// only when it moved.
+///
+/// Registered automatically by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin).
+pub fn update_generation_progress(mut tasks: Query<(&PendingTexture, &mut GenerationProgress)>) {
+    for (pending, mut progress) in &mut tasks {
+        progress.set_if_neq(pending.progress());
+    }
 }
 
-/// Shared constructor body: creates the channel + cancellation flag, spawns the
-/// task, and returns a `PendingTexture`.  The closure `f` is the generator call.
+/// Shared constructor body for generator tasks: `f` is the generator call,
+/// finished on the worker by [`GeneratedTexture::finish`].
+fn spawn_task<F>(f: F, is_card: bool, with_height: bool) -> PendingTexture
+where
+    F: FnOnce() -> Result<TextureMap, TextureError> + Send + 'static,
+{
+    // Finish on the worker too (mip chain, optional height), so the
+    // main-thread upload stays cheap.
+    spawn_finished_task(
+        move |control| {
+            let result = f().map_err(TextureFailure::from);
+            GeneratedTexture::finish(result, is_card, with_height, None, control)
+        },
+        is_card,
+    )
+}
+
+/// Shared constructor body for [`CancellableGenerator`] tasks: the
+/// generator sees the task's flag through a [`GenerationContext`], which
+/// also carries back its height field.
+fn spawn_cancellable_task<G>(
+    generator: G,
+    width: u32,
+    height: u32,
+    is_card: bool,
+    with_height: bool,
+) -> PendingTexture
+where
+    G: CancellableGenerator + Send + 'static,
+{
+    spawn_finished_task(
+        move |control| {
+            let ctx = GenerationContext::new(control, with_height);
+            let result = generator.generate_cancellable(width, height, &ctx);
+            GeneratedTexture::finish(result, is_card, with_height, ctx.into_height(), control)
+        },
+        is_card,
+    )
+}
+
+/// Worker body of every task: skip `f` entirely if the task was cancelled
+/// while queued, otherwise run it and send its result, stamped with its
+/// wall time.  `f` checks the flag itself and fails with
+/// [`TextureFailure::Cancelled`] once it sees it.
+///
+/// A panic in `f` — or in the rayon jobs it fans out to, which re-raise
+/// on this thread — is caught here and sent as
+/// [`TextureFailure::Panicked`]; left to unwind, it would reach rayon's
+/// `spawn` and abort the process.
+fn run_task<F>(f: F, control: &TaskControl, tx: &TaskSender)
+where
+    F: FnOnce(&TaskControl) -> Result<GeneratedTexture, TextureFailure>,
+{
+    if control.is_cancelled() {
+        tx.send(Err(TextureFailure::Cancelled)).ok();
+        return;
+    }
+    control.report(GenerationStage::Pixels, None);
+    let start = bevy::platform::time::Instant::now();
+    let result = match std::panic::catch_unwind(AssertUnwindSafe(|| f(control))) {
+        Ok(result) => result,
+        Err(payload) => {
+            bevy::log::error!(
+                "bevy_symbios_texture: texture generation panicked: {}",
+                panic_message(&*payload)
+            );
+            Err(TextureFailure::Panicked)
+        }
+    };
+    control.report(GenerationStage::Done, Some(1.0));
+    tx.send(result.map(|mut texture| {
+        texture.elapsed = start.elapsed();
+        texture
+    }))
+    .ok();
+}
+
+/// The message a panic was raised with, for logging.
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>")
+}
+
+/// Sending half of a [`PendingTexture`]'s channel.
+type TaskSender = mpsc::SyncSender<Result<GeneratedTexture, TextureFailure>>;
+
+/// Creates the channel + [`TaskControl`], spawns `f`, and returns a
+/// `PendingTexture` fed by its result.  `f` receives the control so it can
+/// report progress and stop early once cancelled; see [`run_task`].
 /// Native Desktop: Spawn using our private, bounded Rayon pool.
 ///
 /// When the rayon pool failed to build (see [`gen_pool`]), the closure runs
@@ -219,64 +1021,173 @@ impl Drop for PendingTexture {
 /// returns, so [`poll_texture_tasks`] still consumes the result correctly via
 /// its normal polling path — only the spawn-time latency changes.
 #[cfg(not(target_arch = "wasm32"))]
-fn spawn_task<F>(f: F, is_card: bool) -> PendingTexture
+fn spawn_finished_task<F>(f: F, is_card: bool) -> PendingTexture
 where
-    F: FnOnce() -> Result<TextureMap, TextureError> + Send + 'static,
+    F: FnOnce(&TaskControl) -> Result<GeneratedTexture, TextureFailure> + Send + 'static,
 {
-    let cancelled = Arc::new(AtomicBool::new(false));
-    let flag = Arc::clone(&cancelled);
+    let control = Arc::new(TaskControl::new());
+    let worker = Arc::clone(&control);
+    let priority = Arc::new(AtomicU8::new(TexturePriority::Normal as u8));
     let (tx, rx) = mpsc::sync_channel(1);
+    let job = move || run_task(f, &worker, &tx);
 
     match gen_pool() {
-        Some(pool) => pool.spawn(move || {
-            if !flag.load(Ordering::Relaxed) {
-                // Compute the mip chain on the worker too, so the
-                // main-thread upload in the polling systems is a pure
-                // buffer move instead of a box-filter pass.
-                tx.send(f().map(TextureMap::with_mips)).ok();
-            }
-        }),
-        None => {
-            if !flag.load(Ordering::Relaxed) {
-                tx.send(f().map(TextureMap::with_mips)).ok();
-            }
+        Some(pool) => {
+            JOB_QUEUE
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(Arc::clone(&priority), Box::new(job));
+            pool.spawn(run_next_job);
         }
+        None => job(),
     }
 
     PendingTexture {
         rx: std::sync::Mutex::new(rx),
-        cancelled,
+        parked: std::sync::Mutex::new(None),
+        control,
+        priority,
         is_card,
+        ready_callback: std::sync::Mutex::new(None),
     }
 }
 
 /// WASM Web: Fallback to Bevy's default AsyncComputeTaskPool.
 /// On WASM, this multiplexes onto the main thread (blocking UI, but compiling cleanly).
+/// Tasks start in submission order; priorities only order uploads.
 #[cfg(target_arch = "wasm32")]
-fn spawn_task<F>(f: F, is_card: bool) -> PendingTexture
+fn spawn_finished_task<F>(f: F, is_card: bool) -> PendingTexture
 where
-    F: FnOnce() -> Result<TextureMap, TextureError> + Send + 'static,
+    F: FnOnce(&TaskControl) -> Result<GeneratedTexture, TextureFailure> + Send + 'static,
 {
     use bevy::tasks::AsyncComputeTaskPool;
 
-    let cancelled = Arc::new(AtomicBool::new(false));
-    let flag = Arc::clone(&cancelled);
+    let control = Arc::new(TaskControl::new());
+    let worker = Arc::clone(&control);
     let (tx, rx) = mpsc::sync_channel(1);
 
     AsyncComputeTaskPool::get()
-        .spawn(async move {
-            if !flag.load(Ordering::Relaxed) {
-                // Compute the mip chain inside the task as on native, so the
-                // polling systems' upload stays a pure buffer move.
-                tx.send(f().map(TextureMap::with_mips)).ok();
-            }
-        })
+        .spawn(async move { run_task(f, &worker, &tx) })
         .detach(); // Detach the Bevy task; we track completion via the mpsc channel anyway
 
     PendingTexture {
         rx: std::sync::Mutex::new(rx),
-        cancelled,
+        parked: std::sync::Mutex::new(None),
+        control,
+        priority: Arc::new(AtomicU8::new(TexturePriority::Normal as u8)),
         is_card,
+        ready_callback: std::sync::Mutex::new(None),
+    }
+}
+
+/// Completion slot shared between a [`PoolJob`] future and its worker.
+struct PoolJobSlot<T> {
+    value: Option<T>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+/// Future resolving to the result of a closure run on the texture-generation
+/// pool — the async counterpart of [`spawn_task`] for callers that already
+/// live inside an executor (the `.symtex` asset loader).
+///
+/// Resolves to `None` if the closure panicked: the job catches the unwind
+/// itself, since a panic escaping a rayon job aborts the process.
+pub(crate) struct PoolJob<T> {
+    slot: Arc<std::sync::Mutex<PoolJobSlot<T>>>,
+}
+
+impl<T> Future for PoolJob<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().expect("pool job slot poisoned");
+        if let Some(value) = slot.value.take() {
+            return Poll::Ready(Some(value));
+        }
+        if slot.finished {
+            return Poll::Ready(None);
+        }
+        slot.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Marks the slot finished and wakes the awaiting task on drop, so a
+/// closure whose panic was caught still resolves its [`PoolJob`] (to `None`)
+/// instead of leaving the awaiting loader pending forever.
+struct PoolJobGuard<T>(Arc<std::sync::Mutex<PoolJobSlot<T>>>);
+
+impl<T> Drop for PoolJobGuard<T> {
+    fn drop(&mut self) {
+        if let Ok(mut slot) = self.0.lock() {
+            slot.finished = true;
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Run `f` on the private texture-generation pool and return a future for
+/// its result.
+///
+/// Falls back to running `f` inline when the pool failed to build (see
+/// [`gen_pool`]) or on WASM, where the asset executor already shares the
+/// main thread.
+pub(crate) fn run_on_pool<T, F>(f: F) -> PoolJob<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let slot = Arc::new(std::sync::Mutex::new(PoolJobSlot {
+        value: None,
+        finished: false,
+        waker: None,
+    }));
+    let guard = PoolJobGuard(Arc::clone(&slot));
+    let job = move || {
+        match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(value) => {
+                if let Ok(mut slot) = guard.0.lock() {
+                    slot.value = Some(value);
+                }
+            }
+            Err(payload) => bevy::log::error!(
+                "bevy_symbios_texture: pool job panicked: {}",
+                panic_message(&*payload)
+            ),
+        }
+        drop(guard);
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    match gen_pool() {
+        Some(pool) => pool.spawn(job),
+        None => job(),
+    }
+    #[cfg(target_arch = "wasm32")]
+    job();
+
+    PoolJob { slot }
+}
+
+/// Run `f` inside the private texture-generation pool, blocking until it
+/// returns.
+///
+/// Rayon parallel iterators started by `f` — and the per-row parallelism
+/// inside every generator — use the pool's threads, so batch callers (the
+/// `symbios-bake` tool, warm-up code) get the same concurrency cap as the
+/// async tasks.  Runs `f` on the calling thread's rayon context when the
+/// pool failed to build.
+pub fn install_in_pool<R, F>(f: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    match gen_pool() {
+        Some(pool) => pool.install(f),
+        None => f(),
     }
 }
 
@@ -302,7 +1213,7 @@ macro_rules! define_pending_constructors {
         /// giving a repeat-wrapping sampler suitable for tileable surfaces.
         pub fn $module(config: $config_ty, width: u32, height: u32) -> Self {
             let generator = <$generator_ty>::new(config);
-            spawn_task(move || generator.generate(width, height), false)
+            spawn_task(move || generator.generate(width, height), false, false)
         }
     };
     (@one $variant:ident, $module:ident, $config_ty:ty, $generator_ty:ty, Card) => {
@@ -316,56 +1227,143 @@ macro_rules! define_pending_constructors {
         /// sampler suitable for alpha-masked cards and sprite atlases.
         pub fn $module(config: $config_ty, width: u32, height: u32) -> Self {
             let generator = <$generator_ty>::new(config);
-            spawn_task(move || generator.generate(width, height), true)
+            spawn_task(move || generator.generate(width, height), true, false)
         }
     };
 }
 
 symbios_texture::for_each_generator!(define_pending_constructors);
 
+/// `tasks` sorted for uploading: highest [`TexturePriority`] first, query
+/// order among equals.
+pub(crate) fn by_priority<T>(
+    tasks: impl IntoIterator<Item = T>,
+    pending: impl Fn(&T) -> &PendingTexture,
+) -> Vec<T> {
+    let mut tasks: Vec<T> = tasks.into_iter().collect();
+    tasks.sort_by_key(|task| std::cmp::Reverse(pending(task).priority()));
+    tasks
+}
+
+/// Bytes of `map`'s pixel buffers, mip chains included.
+pub(crate) fn map_upload_bytes(map: &TextureMap) -> u64 {
+    let emissive = map.emissive.as_ref().map_or(0, Vec::len);
+    (map.albedo.len() + map.normal.len() + map.roughness.len() + emissive) as u64
+}
+
 /// Added to the entity by [`poll_texture_tasks`] when generation is complete.
+///
+/// Observers see it arrive without a polling system — globally, or scoped
+/// to one task's entity:
+///
+/// ```rust,ignore
+/// commands
+///     .spawn(PendingTexture::bark(BarkConfig::default(), 512, 512))
+///     .observe(|ready: On<Add, TextureReady>, textures: Query<&TextureReady>| {
+///         let handles = &textures.get(ready.entity).unwrap().0;
+///     });
+/// ```
+///
+/// [`TextureFailed`] arrives the same way when generation fails.
 #[derive(Component)]
 pub struct TextureReady(pub GeneratedHandles);
 
-/// Bevy system — polls pending generation tasks and uploads finished maps.
+/// Why a texture task produced no images.
+#[derive(Debug)]
+pub enum TextureFailure {
+    /// The generator rejected the request or failed while running.
+    Texture(TextureError),
+    /// The generator panicked on the worker thread.  The panic is caught
+    /// there and reported through the task's channel.
+    Panicked,
+    /// The worker went away without reporting an outcome — e.g. its
+    /// executor dropped the task.
+    Lost,
+    /// The task was stopped by [`PendingTexture::cancel`] before it
+    /// finished.
+    Cancelled,
+}
+
+impl std::fmt::Display for TextureFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Texture(e) => write!(f, "texture generation failed: {e}"),
+            Self::Panicked => f.write_str("texture generation panicked"),
+            Self::Lost => f.write_str("texture generation task was lost"),
+            Self::Cancelled => f.write_str("texture generation was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for TextureFailure {}
+
+impl From<TextureError> for TextureFailure {
+    fn from(e: TextureError) -> Self {
+        Self::Texture(e)
+    }
+}
+
+/// Added to the entity in place of [`TextureReady`] when generation fails,
+/// so apps can swap in a placeholder or retry at a smaller size:
+///
+/// ```rust,ignore
+/// fn on_failed(failed: Query<(Entity, &TextureFailed), Added<TextureFailed>>) {
+///     for (entity, failed) in &failed {
+///         if let TextureFailure::Texture(e) = &failed.0 { /* ... */ }
+///     }
+/// }
+/// ```
 ///
-/// Skips entities also tagged with [`PatchMaterialTextures`](crate::material::PatchMaterialTextures);
+/// Inserted by [`poll_texture_tasks`] and, for a failed layer,
+/// [`poll_texture_array_tasks`](crate::array::poll_texture_array_tasks).
+#[derive(Component, Debug)]
+pub struct TextureFailed(pub TextureFailure);
+
+/// Bevy system — polls pending generation tasks and uploads finished maps,
+/// highest [`TexturePriority`] first and, when an [`UploadBudget`] limit is
+/// set, only those [`plan_texture_uploads`] admitted this frame.  A failed
+/// task leaves a [`TextureFailed`] instead of a [`TextureReady`].
+///
+/// Skips entities also tagged with [`PatchMaterialTextures`](crate::material::PatchMaterialTextures)
+/// (of any material type, via its [`PatchesMaterial`](crate::material::PatchesMaterial) marker);
 /// those are consumed by [`patch_procedural_material_textures`](crate::material::patch_procedural_material_textures)
 /// instead, which writes the generated images directly into a target
-/// `StandardMaterial` rather than emitting [`TextureReady`].
+/// material rather than emitting [`TextureReady`].
 pub fn poll_texture_tasks(
     mut commands: Commands,
     tasks: Query<
         (Entity, &PendingTexture),
-        bevy::ecs::query::Without<crate::material::PatchMaterialTextures>,
+        bevy::ecs::query::Without<crate::material::PatchesMaterial>,
     >,
     mut images: ResMut<Assets<Image>>,
+    mut budget: Option<ResMut<UploadBudget>>,
 ) {
-    for (entity, pending) in &tasks {
-        let poll = pending
-            .rx
-            .lock()
-            .expect("texture thread poisoned")
-            .try_recv();
-        match poll {
-            Ok(Ok(map)) => {
-                let handles = if pending.is_card {
-                    map_to_images_card(map, &mut images)
-                } else {
-                    map_to_images(map, &mut images)
-                };
+    for (entity, pending) in by_priority(tasks.iter(), |task| task.1) {
+        let admitted = take_admission(budget.as_deref_mut(), entity);
+        match pending.try_take(admitted) {
+            Ok(Ok(generated)) => {
+                let handles = generated.upload(pending.is_card, &mut images);
+                pending.run_ready_callback(&handles, &mut commands);
                 commands
                     .entity(entity)
-                    .remove::<PendingTexture>()
+                    .remove::<(PendingTexture, GenerationProgress)>()
                     .insert(TextureReady(handles));
             }
-            Ok(Err(e)) => {
-                bevy::log::error!("Texture generation failed: {e}");
-                commands.entity(entity).remove::<PendingTexture>();
+            Ok(Err(failure)) => {
+                if !matches!(failure, TextureFailure::Cancelled) {
+                    bevy::log::error!("{failure}");
+                }
+                commands
+                    .entity(entity)
+                    .remove::<(PendingTexture, GenerationProgress)>()
+                    .insert(TextureFailed(failure));
             }
             Err(mpsc::TryRecvError::Disconnected) => {
-                bevy::log::error!("Texture generation thread panicked");
-                commands.entity(entity).remove::<PendingTexture>();
+                bevy::log::error!("{}", TextureFailure::Lost);
+                commands
+                    .entity(entity)
+                    .remove::<(PendingTexture, GenerationProgress)>()
+                    .insert(TextureFailed(TextureFailure::Lost));
             }
             Err(mpsc::TryRecvError::Empty) => {}
         }
@@ -380,14 +1378,20 @@ mod tests {
     /// Auto thread count picks at least one thread regardless of host parallelism.
     #[test]
     fn auto_pool_threads_is_at_least_one() {
-        let cfg = AsyncTextureConfig { pool_threads: 0 };
+        let cfg = AsyncTextureConfig {
+            pool_threads: 0,
+            ..AsyncTextureConfig::default()
+        };
         assert!(resolve_pool_threads(&cfg) >= 1);
     }
 
     /// Explicit non-zero values are passed through unchanged.
     #[test]
     fn explicit_pool_threads_is_passthrough() {
-        let cfg = AsyncTextureConfig { pool_threads: 7 };
+        let cfg = AsyncTextureConfig {
+            pool_threads: 7,
+            ..AsyncTextureConfig::default()
+        };
         assert_eq!(resolve_pool_threads(&cfg), 7);
     }
 
@@ -413,4 +1417,282 @@ mod tests {
         assert_eq!(map.width, 8);
         assert_eq!(map.height, 8);
     }
+
+    /// The queue hands out the highest priority first, oldest first among
+    /// equals, and honours priorities changed while jobs wait.
+    #[test]
+    fn job_queue_pops_by_priority_then_age() {
+        let ran = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut queue = JobQueue::new();
+        let mut priorities = Vec::new();
+        for (name, priority) in [
+            ("a", TexturePriority::Normal),
+            ("b", TexturePriority::Prefetch),
+            ("c", TexturePriority::Normal),
+            ("d", TexturePriority::Visible),
+        ] {
+            let ran = Arc::clone(&ran);
+            let priority = Arc::new(AtomicU8::new(priority as u8));
+            priorities.push(Arc::clone(&priority));
+            queue.push(priority, Box::new(move || ran.lock().unwrap().push(name)));
+        }
+        // Promote the prefetch after it was queued.
+        priorities[1].store(TexturePriority::Visible as u8, Ordering::Relaxed);
+
+        while let Some(job) = queue.pop() {
+            job();
+        }
+        assert_eq!(*ran.lock().unwrap(), ["b", "d", "a", "c"]);
+    }
+
+    /// A task cancelled after its generator returned neither recovers the
+    /// height field nor builds mips, and says so.
+    #[test]
+    fn cancelled_finish_skips_remaining_stages() {
+        let map = BarkGenerator::new(BarkConfig::default())
+            .generate(16, 16)
+            .unwrap();
+        let control = TaskControl::new();
+        control.cancel();
+        assert!(matches!(
+            GeneratedTexture::finish(Ok(map), false, true, None, &control),
+            Err(TextureFailure::Cancelled)
+        ));
+        assert_ne!(control.progress().stage, GenerationStage::Mips);
+    }
+
+    /// Progress round-trips through the packed atomic, and `finish` walks
+    /// the height and mip stages in order.
+    #[test]
+    fn task_control_reports_stage_and_fraction() {
+        let control = TaskControl::new();
+        assert_eq!(control.progress(), GenerationProgress::default());
+
+        control.report(GenerationStage::Height, Some(0.5));
+        let progress = control.progress();
+        assert_eq!(progress.stage, GenerationStage::Height);
+        assert!((progress.fraction.unwrap() - 0.5).abs() < 1e-4);
+
+        control.report(GenerationStage::Pixels, None);
+        assert_eq!(
+            control.progress(),
+            GenerationProgress {
+                stage: GenerationStage::Pixels,
+                fraction: None,
+            }
+        );
+
+        let map = BarkGenerator::new(BarkConfig::default())
+            .generate(16, 16)
+            .unwrap();
+        GeneratedTexture::finish(Ok(map), false, true, None, &control).unwrap();
+        assert_eq!(control.progress().stage, GenerationStage::Mips);
+        assert_eq!(control.progress().fraction, None);
+    }
+
+    /// A `PendingTexture` whose result is already waiting in its channel.
+    fn finished(width: u32, priority: TexturePriority) -> PendingTexture {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let map = BarkGenerator::new(BarkConfig::default())
+            .generate(width, width)
+            .unwrap();
+        let control = Arc::new(TaskControl::new());
+        tx.send(GeneratedTexture::finish(
+            Ok(map),
+            false,
+            false,
+            None,
+            &control,
+        ))
+        .unwrap();
+        PendingTexture {
+            rx: std::sync::Mutex::new(rx),
+            parked: std::sync::Mutex::new(None),
+            control,
+            priority: Arc::new(AtomicU8::new(priority as u8)),
+            is_card: false,
+            ready_callback: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// With a budget of one texture per frame, a burst of completions
+    /// uploads one per frame, most urgent first.
+    #[test]
+    fn upload_budget_spreads_completions_across_frames() {
+        use bevy::ecs::{
+            schedule::{IntoScheduleConfigs, Schedule},
+            world::World,
+        };
+
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        let bytes = finished(8, TexturePriority::Normal)
+            .ready_upload_bytes()
+            .unwrap();
+        world.insert_resource(UploadBudget::new(Some(bytes)));
+        let normal = world.spawn(finished(8, TexturePriority::Normal)).id();
+        let prefetch = world.spawn(finished(8, TexturePriority::Prefetch)).id();
+        let visible = world.spawn(finished(8, TexturePriority::Visible)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                reset_upload_budget,
+                plan_texture_uploads,
+                poll_texture_tasks,
+            )
+                .chain(),
+        );
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            schedule.run(&mut world);
+            for entity in [normal, prefetch, visible] {
+                if world.get::<TextureReady>(entity).is_some() && !order.contains(&entity) {
+                    order.push(entity);
+                }
+            }
+        }
+        assert_eq!(order, [visible, normal, prefetch]);
+    }
+
+    /// An `on_ready` callback runs once, with the uploaded handles, in
+    /// the frame the task completes.
+    #[test]
+    fn on_ready_runs_with_uploaded_handles() {
+        use bevy::ecs::{schedule::Schedule, world::World};
+
+        #[derive(Component)]
+        struct Seen(bevy::asset::Handle<Image>);
+
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        let entity = world
+            .spawn(
+                finished(8, TexturePriority::Normal).on_ready(|handles, commands| {
+                    commands.spawn(Seen(handles.albedo.clone()));
+                }),
+            )
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(poll_texture_tasks);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let ready = world.get::<TextureReady>(entity).expect("task completed");
+        let albedo = ready.0.albedo.id();
+        let mut seen = world.query::<&Seen>();
+        let seen: Vec<_> = seen.iter(&world).map(|seen| seen.0.id()).collect();
+        assert_eq!(seen, [albedo]);
+    }
+
+    /// A task whose worker vanished without sending leaves a
+    /// `TextureFailed` to react to.
+    #[test]
+    fn lost_worker_leaves_texture_failed() {
+        use bevy::ecs::{schedule::Schedule, world::World};
+
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        let (_, rx) = mpsc::sync_channel(1);
+        let entity = world
+            .spawn(PendingTexture {
+                rx: std::sync::Mutex::new(rx),
+                parked: std::sync::Mutex::new(None),
+                control: Arc::new(TaskControl::new()),
+                priority: Arc::new(AtomicU8::new(TexturePriority::Normal as u8)),
+                is_card: false,
+                ready_callback: std::sync::Mutex::new(None),
+            })
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(poll_texture_tasks);
+        schedule.run(&mut world);
+
+        let entity = world.entity(entity);
+        assert!(!entity.contains::<PendingTexture>());
+        assert!(!entity.contains::<TextureReady>());
+        assert!(matches!(
+            entity.get::<TextureFailed>(),
+            Some(TextureFailed(TextureFailure::Lost))
+        ));
+    }
+
+    /// A generator that panics on the pool is reported as
+    /// `TextureFailure::Panicked` instead of taking the process down.
+    #[test]
+    fn panicking_generator_reports_panicked() {
+        let pending = spawn_task(|| panic!("generator exploded"), false, false);
+        let deadline = std::time::Instant::now() + Duration::from_secs(30);
+        let result = loop {
+            match pending.try_take(true) {
+                Ok(result) => break result,
+                Err(mpsc::TryRecvError::Empty) => {
+                    assert!(
+                        std::time::Instant::now() < deadline,
+                        "panicking task never reported"
+                    );
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => panic!("worker lost the task"),
+            }
+        };
+        assert!(matches!(result, Err(TextureFailure::Panicked)));
+    }
+
+    /// A closure that panics on the pool resolves its `PoolJob` to `None`
+    /// (surfaced by the loader as `SymtexLoadError::Panicked`) instead of
+    /// aborting the process or leaving the future pending.
+    #[test]
+    fn panicking_pool_job_resolves_to_none() {
+        let job = run_on_pool(|| -> u32 { panic!("loader job exploded") });
+        assert_eq!(bevy::tasks::block_on(job), None);
+        assert_eq!(bevy::tasks::block_on(run_on_pool(|| 7u32)), Some(7));
+    }
+
+    /// A task spawned with `spawn_with_height` delivers a full-resolution
+    /// `R16Float` height image alongside the other maps; a plain `spawn`
+    /// does not.
+    #[test]
+    fn height_is_uploaded_only_when_requested() {
+        use bevy::ecs::{schedule::Schedule, world::World};
+
+        use crate::material::TextureConfig;
+
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        let config = TextureConfig::Bark(BarkConfig::default());
+        let with = world.spawn(config.spawn_with_height(16, 8).unwrap()).id();
+        let without = world.spawn(config.spawn(16, 8).unwrap()).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(poll_texture_tasks);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while world.get::<TextureReady>(with).is_none()
+            || world.get::<TextureReady>(without).is_none()
+        {
+            assert!(std::time::Instant::now() < deadline, "generation timed out");
+            schedule.run(&mut world);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert!(
+            world
+                .get::<TextureReady>(without)
+                .unwrap()
+                .0
+                .height
+                .is_none()
+        );
+        let height = world.get::<TextureReady>(with).unwrap().0.height.clone();
+        let images = world.resource::<Assets<Image>>();
+        let image = images.get(&height.expect("height handle")).unwrap();
+        assert_eq!(
+            image.texture_descriptor.format,
+            bevy::render::render_resource::TextureFormat::R16Float
+        );
+        assert_eq!(image.width(), 16);
+        assert_eq!(image.height(), 8);
+    }
 }
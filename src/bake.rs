@@ -0,0 +1,387 @@
+//! Offline baking: generate a manifest of texture configs ahead of time.
+//!
+//! Backs the `symbios-bake` binary, which warms a [`FileStore`] cache
+//! directory before the application launches or exports image files for
+//! shipping.  A manifest lists the textures to produce; like `.symtex`
+//! files it is JSON when the file name ends in `.json` and RON otherwise:
+//!
+//! ```json
+//! {
+//!   "entries": [
+//!     { "texture": { "$type": "Brick", "seed": 7, "scale": 4.0, … }, "width": 1024, "height": 1024 },
+//!     { "texture": { "$type": "Leaf", … }, "width": 512, "height": 512, "name": "oak_leaf" }
+//!   ]
+//! }
+//! ```
+//!
+//! Each `texture` is a full [`TextureConfig`] in its serde form (the same
+//! shape `.symtex` files and `serde_json::to_string` use).  Entries are
+//! generated in parallel on the same private pool the async tasks use (see
+//! [`install_in_pool`]), so [`AsyncTextureConfig::pool_threads`] caps the
+//! bake exactly as it caps runtime generation.
+//!
+//! [`AsyncTextureConfig::pool_threads`]: crate::AsyncTextureConfig::pool_threads
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::async_gen::install_in_pool;
+use crate::cache::{BlobCompression, FileStore, TextureCacheKey};
+use crate::export::{ExportError, Ktx2ExportOptions, PngExportOptions, export_ktx2, export_png};
+use crate::generator::TextureError;
+use crate::material::TextureConfig;
+
+/// One texture to bake.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BakeEntry {
+    /// Generator config.  [`TextureConfig::None`] is rejected at bake time.
+    pub texture: TextureConfig,
+    /// Texture width in texels.
+    pub width: u32,
+    /// Texture height in texels.
+    pub height: u32,
+    /// File stem for exported images.  Defaults to
+    /// `<kind>_<fingerprint>_<width>x<height>`; ignored when warming a cache,
+    /// whose file names derive from the cache key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl BakeEntry {
+    /// Cache key this entry is stored under by [`FileStore`].
+    pub fn cache_key(&self) -> TextureCacheKey {
+        TextureCacheKey {
+            kind: self.texture.label(),
+            fingerprint: self.texture.fingerprint(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// [`name`](BakeEntry::name), or the default stem derived from the
+    /// cache key.
+    pub fn stem(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            format!(
+                "{}_{:016x}_{}x{}",
+                self.texture.label().to_lowercase(),
+                self.texture.fingerprint(),
+                self.width,
+                self.height
+            )
+        })
+    }
+}
+
+/// The list of textures a bake produces.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct BakeManifest {
+    /// Textures to generate, reported in this order.
+    pub entries: Vec<BakeEntry>,
+}
+
+/// Where baked textures go.
+#[derive(Clone, Debug)]
+pub enum BakeOutput {
+    /// Warm a [`FileStore`] directory so a `TextureCache::file(dir,
+    /// manifest_version)` opened by the application hits on first use.
+    Cache {
+        /// Cache directory, created if missing.
+        dir: PathBuf,
+        /// Must match the application's `TextureCache::manifest_version`.
+        manifest_version: u32,
+        /// Blob encoding; the application reads either kind.
+        compression: BlobCompression,
+    },
+    /// Write PNG files (see [`export_png`]).
+    Png {
+        /// Output directory, created if missing.
+        dir: PathBuf,
+        /// Export options, e.g. one file per mip level.
+        options: PngExportOptions,
+    },
+    /// Write KTX2 files with full mip chains (see [`export_ktx2`]).
+    Ktx2 {
+        /// Output directory, created if missing.
+        dir: PathBuf,
+    },
+}
+
+/// Errors raised while reading a manifest or baking an entry.
+#[derive(Debug)]
+pub enum BakeError {
+    /// Reading the manifest or opening the output failed.
+    Io(std::io::Error),
+    /// The manifest is not valid RON.
+    Ron(ron::error::SpannedError),
+    /// The manifest is not valid JSON.
+    Json(serde_json::Error),
+    /// The entry's config is [`TextureConfig::None`].
+    NoTexture,
+    /// The generator rejected the entry's dimensions.
+    Texture(TextureError),
+    /// Writing the entry's output failed.
+    Export(ExportError),
+}
+
+impl std::fmt::Display for BakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Ron(e) => write!(f, "invalid manifest RON: {e}"),
+            Self::Json(e) => write!(f, "invalid manifest JSON: {e}"),
+            Self::NoTexture => f.write_str("entry has no texture config (\"None\")"),
+            Self::Texture(e) => write!(f, "generation failed: {e}"),
+            Self::Export(e) => write!(f, "export failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BakeError {}
+
+impl From<std::io::Error> for BakeError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Parse a manifest body, choosing JSON or RON by the file name.
+pub fn parse_bake_manifest(bytes: &[u8], path: &Path) -> Result<BakeManifest, BakeError> {
+    let is_json = path.extension().is_some_and(|ext| ext == "json");
+    if is_json {
+        serde_json::from_slice(bytes).map_err(BakeError::Json)
+    } else {
+        ron::de::from_bytes(bytes).map_err(BakeError::Ron)
+    }
+}
+
+/// Progress notification passed to the [`bake`] callback as each entry
+/// finishes, in completion order.
+pub struct BakeProgress<'a> {
+    /// Entries finished so far, including this one.
+    pub completed: usize,
+    /// Entries in the manifest.
+    pub total: usize,
+    /// Index of this entry in the manifest.
+    pub index: usize,
+    /// The entry itself.
+    pub entry: &'a BakeEntry,
+    /// Wall-clock time spent generating and writing this entry.
+    pub elapsed: Duration,
+    /// Why the entry failed, if it did.
+    pub error: Option<&'a BakeError>,
+}
+
+/// Outcome of a [`bake`] run.
+#[derive(Debug, Default)]
+pub struct BakeReport {
+    /// Entries baked successfully.
+    pub succeeded: usize,
+    /// Failed entries as `(manifest index, error)`, in manifest order.
+    pub failures: Vec<(usize, BakeError)>,
+}
+
+impl BakeReport {
+    /// `true` when every entry baked.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Generate every entry of `manifest` in parallel and write it to `output`.
+///
+/// A failing entry does not stop the others; it is reported to `progress`
+/// and collected in the [`BakeReport`].  Returns `Err` only when the output
+/// itself cannot be opened.
+pub fn bake<P>(
+    manifest: &BakeManifest,
+    output: &BakeOutput,
+    progress: P,
+) -> Result<BakeReport, BakeError>
+where
+    P: Fn(BakeProgress<'_>) + Sync,
+{
+    let store = match output {
+        BakeOutput::Cache {
+            dir,
+            manifest_version,
+            compression,
+        } => Some(
+            FileStore::with_manifest_version(dir.clone(), *manifest_version)?
+                .with_compression(*compression),
+        ),
+        BakeOutput::Png { .. } | BakeOutput::Ktx2 { .. } => None,
+    };
+
+    let total = manifest.entries.len();
+    let completed = AtomicUsize::new(0);
+    let results: Vec<Result<(), BakeError>> = install_in_pool(|| {
+        manifest
+            .entries
+            .par_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let start = Instant::now();
+                let result = bake_entry(entry, output, store.as_ref());
+                progress(BakeProgress {
+                    completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                    total,
+                    index,
+                    entry,
+                    elapsed: start.elapsed(),
+                    error: result.as_ref().err(),
+                });
+                result
+            })
+            .collect()
+    });
+
+    let mut report = BakeReport::default();
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(()) => report.succeeded += 1,
+            Err(e) => report.failures.push((index, e)),
+        }
+    }
+    Ok(report)
+}
+
+fn bake_entry(
+    entry: &BakeEntry,
+    output: &BakeOutput,
+    store: Option<&FileStore>,
+) -> Result<(), BakeError> {
+    let map = entry
+        .texture
+        .generate_sync(entry.width, entry.height)
+        .ok_or(BakeError::NoTexture)?
+        .map_err(BakeError::Texture)?;
+    let is_card = entry.texture.render_properties().is_card;
+
+    match output {
+        BakeOutput::Cache { .. } => {
+            let store = store.expect("cache output opens a FileStore");
+            store.write_entry(&entry.cache_key(), &map, is_card)?;
+        }
+        BakeOutput::Png { dir, options } => {
+            export_png(&map, dir, &entry.stem(), options).map_err(BakeError::Export)?;
+        }
+        BakeOutput::Ktx2 { dir } => {
+            let options = Ktx2ExportOptions { is_card };
+            export_ktx2(&map.with_mips(), dir, &entry.stem(), &options)
+                .map_err(BakeError::Export)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    use bevy::asset::Assets;
+    use bevy::image::Image;
+
+    use crate::brick::BrickConfig;
+    use crate::cache::TextureCacheStore;
+    use crate::leaf::LeafConfig;
+
+    /// Two bakeable entries followed by two that must fail.
+    fn manifest() -> BakeManifest {
+        let entry = |texture, width, name: Option<&str>| BakeEntry {
+            texture,
+            width,
+            height: 8,
+            name: name.map(str::to_owned),
+        };
+        BakeManifest {
+            entries: vec![
+                entry(
+                    TextureConfig::Brick(BrickConfig {
+                        seed: 3,
+                        ..BrickConfig::default()
+                    }),
+                    8,
+                    None,
+                ),
+                entry(TextureConfig::Leaf(LeafConfig::default()), 8, Some("leaf")),
+                entry(TextureConfig::None, 8, None),
+                entry(TextureConfig::Brick(BrickConfig::default()), 0, None),
+            ],
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bst-bake-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn warms_file_store_and_reports_failures() {
+        let json = serde_json::to_vec(&manifest()).expect("serialise");
+        let manifest = parse_bake_manifest(&json, Path::new("m.json")).expect("parse");
+        assert!(matches!(&manifest.entries[0].texture, TextureConfig::Brick(b) if b.seed == 3));
+        assert_eq!(manifest.entries[1].name.as_deref(), Some("leaf"));
+
+        let dir = temp_dir("cache");
+        let output = BakeOutput::Cache {
+            dir: dir.clone(),
+            manifest_version: 4,
+            compression: BlobCompression::Deflate,
+        };
+        let seen = Mutex::new(Vec::new());
+        let report = bake(&manifest, &output, |p| {
+            assert_eq!(p.total, 4);
+            seen.lock().unwrap().push((p.index, p.error.is_some()));
+        })
+        .expect("open cache dir");
+
+        assert_eq!(report.succeeded, 2);
+        assert!(!report.is_success());
+        let failed: Vec<_> = report.failures.iter().map(|(i, _)| *i).collect();
+        assert_eq!(failed, [2, 3]);
+        assert!(matches!(report.failures[0].1, BakeError::NoTexture));
+        assert!(matches!(report.failures[1].1, BakeError::Texture(_)));
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, [(0, false), (1, false), (2, true), (3, true)]);
+
+        // The application's store under the same version hits; another
+        // version does not.
+        let mut images = Assets::<Image>::default();
+        let key = manifest.entries[1].cache_key();
+        let mut store = FileStore::with_manifest_version(dir.clone(), 4).expect("open");
+        assert!(store.get(&key, &mut images).is_some());
+        let mut other = FileStore::with_manifest_version(dir.clone(), 5).expect("open");
+        assert!(other.get(&key, &mut images).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn exports_named_and_default_stems() {
+        let manifest = BakeManifest {
+            entries: manifest().entries.into_iter().take(2).collect(),
+        };
+        let dir = temp_dir("ktx2");
+        let report = bake(&manifest, &BakeOutput::Ktx2 { dir: dir.clone() }, |_| {}).expect("bake");
+        assert!(report.is_success());
+
+        let leaf = std::fs::read(dir.join("leaf_albedo.ktx2")).expect("named stem");
+        assert_eq!(
+            crate::export::ktx2_address_mode(&leaf),
+            Some(bevy::image::ImageAddressMode::ClampToEdge),
+            "cards record clamp-to-edge"
+        );
+        let brick = dir.join(format!("{}_orm.ktx2", manifest.entries[0].stem()));
+        assert!(brick.exists(), "default stem {}", brick.display());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,182 @@
+//! `symbios-bake` — generate a manifest of textures offline.
+//!
+//! Warms a [`FileStore`](bevy_symbios_texture::FileStore) directory before
+//! the application launches, or exports PNG / KTX2 files for shipping.  See
+//! [`bevy_symbios_texture::bake`] for the manifest format.
+//!
+//! Exits with status 0 when every entry baked, 1 when any entry failed, and
+//! 2 on a usage or manifest error.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use bevy_symbios_texture::AsyncTextureConfig;
+use bevy_symbios_texture::async_gen::set_pool_config;
+use bevy_symbios_texture::bake::{BakeOutput, bake, parse_bake_manifest};
+use bevy_symbios_texture::cache::BlobCompression;
+use bevy_symbios_texture::export::PngExportOptions;
+
+const USAGE: &str = "\
+usage: symbios-bake <MANIFEST> <OUTPUT> [--threads N]
+
+MANIFEST is a .json or .ron bake manifest.  OUTPUT is one of:
+    --cache-dir DIR [--manifest-version N] [--compress]
+                                             warm a FileStore cache (version defaults to 0),
+                                             optionally with compressed blobs
+    --png DIR [--mips]                       write PNG files, optionally every mip level
+    --ktx2 DIR                               write KTX2 files with full mip chains
+
+    --threads N   generation threads (0 = half the cores; default 0)";
+
+/// Parsed command line.
+struct Args {
+    manifest: PathBuf,
+    output: BakeOutput,
+    threads: usize,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut manifest = None;
+    let mut cache_dir = None;
+    let mut manifest_version = None;
+    let mut compress = false;
+    let mut png_dir = None;
+    let mut mips = false;
+    let mut ktx2_dir = None;
+    let mut threads = 0;
+
+    let value = |args: &mut dyn Iterator<Item = String>, flag: &str| {
+        args.next().ok_or_else(|| format!("{flag} needs a value"))
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cache-dir" => cache_dir = Some(PathBuf::from(value(&mut args, &arg)?)),
+            "--manifest-version" => {
+                let v = value(&mut args, &arg)?;
+                manifest_version = Some(
+                    v.parse::<u32>()
+                        .map_err(|_| format!("--manifest-version: not a u32: {v}"))?,
+                );
+            }
+            "--compress" => compress = true,
+            "--png" => png_dir = Some(PathBuf::from(value(&mut args, &arg)?)),
+            "--mips" => mips = true,
+            "--ktx2" => ktx2_dir = Some(PathBuf::from(value(&mut args, &arg)?)),
+            "--threads" => {
+                let v = value(&mut args, &arg)?;
+                threads = v
+                    .parse()
+                    .map_err(|_| format!("--threads: not a count: {v}"))?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            _ if manifest.is_none() => manifest = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+
+    let manifest = manifest.ok_or("missing MANIFEST")?;
+    let output = match (cache_dir, png_dir, ktx2_dir) {
+        (Some(dir), None, None) => BakeOutput::Cache {
+            dir,
+            manifest_version: manifest_version.unwrap_or(0),
+            compression: if compress {
+                BlobCompression::Deflate
+            } else {
+                BlobCompression::None
+            },
+        },
+        (None, Some(dir), None) => BakeOutput::Png {
+            dir,
+            options: PngExportOptions { mip_levels: mips },
+        },
+        (None, None, Some(dir)) => BakeOutput::Ktx2 { dir },
+        (None, None, None) => return Err("missing OUTPUT".into()),
+        _ => return Err("choose exactly one of --cache-dir, --png, --ktx2".into()),
+    };
+    if manifest_version.is_some() && !matches!(output, BakeOutput::Cache { .. }) {
+        return Err("--manifest-version only applies to --cache-dir".into());
+    }
+    if compress && !matches!(output, BakeOutput::Cache { .. }) {
+        return Err("--compress only applies to --cache-dir".into());
+    }
+    if mips && !matches!(output, BakeOutput::Png { .. }) {
+        return Err("--mips only applies to --png".into());
+    }
+    Ok(Args {
+        manifest,
+        output,
+        threads,
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("symbios-bake: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let manifest = match std::fs::read(&args.manifest)
+        .map_err(Into::into)
+        .and_then(|bytes| parse_bake_manifest(&bytes, &args.manifest))
+    {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("symbios-bake: {}: {e}", args.manifest.display());
+            return ExitCode::from(2);
+        }
+    };
+
+    // First and only configuration in this process; cannot already be set.
+    let _ = set_pool_config(AsyncTextureConfig {
+        pool_threads: args.threads,
+        ..AsyncTextureConfig::default()
+    });
+
+    let start = Instant::now();
+    let report = bake(&manifest, &args.output, |p| {
+        let status = match p.error {
+            None => "ok".to_owned(),
+            Some(e) => format!("FAILED: {e}"),
+        };
+        // One write per line so parallel workers do not interleave.
+        let line = format!(
+            "[{}/{}] #{} {} {}x{} ({:.0?}) {status}\n",
+            p.completed,
+            p.total,
+            p.index,
+            p.entry.texture.label(),
+            p.entry.width,
+            p.entry.height,
+            p.elapsed,
+        );
+        let _ = std::io::stderr().write_all(line.as_bytes());
+    });
+
+    match report {
+        Ok(report) => {
+            eprintln!(
+                "baked {} of {} entries in {:.1?}",
+                report.succeeded,
+                manifest.entries.len(),
+                start.elapsed()
+            );
+            if report.is_success() {
+                ExitCode::SUCCESS
+            } else {
+                for (index, e) in &report.failures {
+                    eprintln!("  #{index}: {e}");
+                }
+                ExitCode::FAILURE
+            }
+        }
+        Err(e) => {
+            eprintln!("symbios-bake: cannot open output: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -8,18 +8,41 @@
 //!
 //! Two storage backends ship with the crate:
 //!
-//! * [`MemoryStore`] — process-local `HashMap` bounded by `max_entries`.
-//!   When the cap is reached, the oldest entry (by insertion order) is
-//!   dropped.  Re-inserting an existing key updates in place without
-//!   evicting.  Use this for per-app caches (default biomes warm-up, hot
-//!   parameter sweeps).
+//! * [`MemoryStore`] — process-local `HashMap` bounded by `max_entries` and
+//!   an optional byte budget.  When either cap is exceeded, the least
+//!   recently used entry (lookups count as use) is dropped.  Re-inserting an
+//!   existing key updates in place.  Use this for per-app caches (default
+//!   biomes warm-up, hot parameter sweeps).
 //! * [`FileStore`] — disk-backed key/value store keyed by the standard
 //!   library hash (currently SipHash-1-3 via `DefaultHasher`) of the cache
-//!   key.  Survives process restarts and lets a CLI tool warm the cache
-//!   from a manifest before the application launches.  Stored blobs are
-//!   raw RGBA8 base levels (albedo + normal + ORM) — mipmaps are
+//!   key.  Survives process restarts and lets the `symbios-bake` tool (see
+//!   [`bake`](crate::bake)) warm the cache from a manifest before the
+//!   application launches.  Stored blobs are
+//!   RGBA8 base levels (albedo + normal + ORM), raw or — with
+//!   [`BlobCompression::Deflate`] — losslessly compressed; mipmaps are
 //!   regenerated on upload by [`map_to_images`] / [`map_to_images_card`].
 //!
+//! [`FileStore`] can be capped with a disk budget
+//! ([`FileStore::with_disk_budget`]): writes that push the directory over it
+//! prune the least recently used blobs, tracked in an access-time index next
+//! to them.  [`FileStore::prune_stale_manifests`] reclaims the blobs a
+//! `manifest_version` bump left behind.
+//!
+//! Blobs carry an FNV-1a checksum and are written through a temporary file
+//! and a rename, so a crash mid-write never leaves a partial blob.  A blob
+//! that fails validation on load is treated as a miss, logged and deleted.
+//!
+//! Disk hits requested through
+//! [`build_procedural_material_async`] do not block the calling system: the
+//! store hands out a [`PixelLoader`] ([`TextureCacheStore::pixel_loader`])
+//! that reads and decodes the blob on the texture-generation pool, and the
+//! patch system uploads the result like a freshly generated map.
+//!
+//! [`TextureCache::stats`] reports hits, disk hits, misses, evictions,
+//! resident bytes and generation wall time per generator kind, and
+//! [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) publishes the totals
+//! as [`bevy::diagnostic`] measurements (see [`CacheDiagnostics`]).
+//!
 //! Cache invalidation is driven by [`TextureConfig::fingerprint`]: any change
 //! to a config field rolls the fingerprint and therefore the
 //! [`TextureCacheKey`], so previously-cached entries become unreachable
@@ -34,15 +57,18 @@
 //! [`build_procedural_material_async`]: crate::material::build_procedural_material_async
 //! [`GeneratedHandles`]: crate::generator::GeneratedHandles
 
-use std::collections::HashMap;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use bevy::asset::Assets;
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics};
 use bevy::ecs::resource::Resource;
+use bevy::ecs::system::Res;
 use bevy::image::Image;
 
 use crate::generator::{GeneratedHandles, TextureMap, map_to_images, map_to_images_card};
@@ -72,6 +98,11 @@ pub struct TextureCacheKey {
     pub height: u32,
 }
 
+/// Deferred read of a cache entry's persisted pixels, run off the main
+/// thread.  Resolves to the base-level map, or `None` when the entry turns
+/// out to be missing or unreadable.
+pub type PixelLoader = Box<dyn FnOnce() -> Option<TextureMap> + Send>;
+
 /// Trait implemented by texture cache backends.
 ///
 /// Implementations must be `Send + Sync` — Bevy's resource lookup hands
@@ -129,6 +160,22 @@ pub trait TextureCacheStore: Send + Sync {
     fn peek_memory_only(&self, _key: &TextureCacheKey) -> Option<Arc<GeneratedHandles>> {
         None
     }
+
+    /// Optional asynchronous path for backends whose hits need I/O.
+    ///
+    /// Returning a [`PixelLoader`] tells
+    /// [`build_procedural_material_async`](crate::material::build_procedural_material_async)
+    /// to skip [`get`](TextureCacheStore::get) for `key` and instead run the
+    /// loader on the texture-generation pool, falling back to generation
+    /// when it yields `None`.  The loader must not assume the entry exists.
+    /// The default — `None` — keeps lookups on the synchronous `get` path.
+    fn pixel_loader(&self, _key: &TextureCacheKey) -> Option<PixelLoader> {
+        None
+    }
+
+    /// Add what only the backend knows — evictions and resident bytes — to
+    /// a [`TextureCache::stats`] snapshot.  The default reports nothing.
+    fn report_usage(&self, _stats: &mut CacheStats) {}
 }
 
 /// Bevy resource wrapper for any [`TextureCacheStore`] implementation.
@@ -151,6 +198,10 @@ pub struct TextureCache {
     /// Memory-backed stores never outlive the process and ignore it.
     pub manifest_version: u32,
     inner: Mutex<Box<dyn TextureCacheStore>>,
+    /// Hit / miss / generation counters kept by the cache itself; the store
+    /// adds its own figures in [`stats`](TextureCache::stats).  Shared with
+    /// the [`PixelLoader`]s it hands out, which count their own outcome.
+    stats: Arc<Mutex<CacheStats>>,
 }
 
 impl TextureCache {
@@ -159,14 +210,41 @@ impl TextureCache {
         Self {
             manifest_version,
             inner: Mutex::new(store),
+            stats: Arc::new(Mutex::new(CacheStats::default())),
         }
     }
 
-    /// Convenience: in-memory cache with the default capacity.
+    /// Convenience: in-memory cache holding at most `max_entries` entries.
     pub fn memory(max_entries: usize) -> Self {
         Self::new(Box::new(MemoryStore::new(max_entries)), 0)
     }
 
+    /// Convenience: in-memory cache bounded by both `max_entries` and an
+    /// estimated `max_bytes` of texture memory (see
+    /// [`MemoryStore::with_byte_budget`]).
+    pub fn memory_with_byte_budget(max_entries: usize, max_bytes: u64) -> Self {
+        Self::new(
+            Box::new(MemoryStore::with_byte_budget(max_entries, max_bytes)),
+            0,
+        )
+    }
+
+    /// Convenience: file-backed cache rooted at `dir`, holding at most
+    /// `max_bytes` of blobs (see [`FileStore::with_disk_budget`]).
+    pub fn file_with_disk_budget(
+        dir: impl Into<PathBuf>,
+        manifest_version: u32,
+        max_bytes: u64,
+    ) -> std::io::Result<Self> {
+        Ok(Self::new(
+            Box::new(
+                FileStore::with_manifest_version(dir.into(), manifest_version)?
+                    .with_disk_budget(max_bytes),
+            ),
+            manifest_version,
+        ))
+    }
+
     /// Convenience: file-backed cache rooted at `dir`.
     ///
     /// The directory is created if missing.  Each entry produces one
@@ -193,15 +271,47 @@ impl TextureCache {
     /// their blob and upload it into `images` here, so a hit returns handles
     /// ready to assign to `StandardMaterial` slots.
     ///
+    /// This blocks on the read;
     /// [`build_procedural_material_async`](crate::material::build_procedural_material_async)
-    /// uses this, which is what makes disk-backed caches short-circuit
-    /// generation exactly like memory-backed ones.
+    /// only falls back to it for backends without a
+    /// [`pixel_loader`](TextureCache::pixel_loader).
+    ///
+    /// Counts a hit, a disk hit or a miss in [`stats`](TextureCache::stats).
     pub fn get(
         &self,
         key: &TextureCacheKey,
         images: &mut Assets<Image>,
     ) -> Option<Arc<GeneratedHandles>> {
-        self.inner.lock().ok()?.get(key, images)
+        let mut store = self.inner.lock().ok()?;
+        if let Some(handles) = store.peek_memory_only(key) {
+            self.record(key.kind, |kind| kind.hits += 1);
+            return Some(handles);
+        }
+        let handles = store.get(key, images);
+        match handles {
+            Some(_) => self.record(key.kind, |kind| kind.disk_hits += 1),
+            None => self.record(key.kind, |kind| kind.misses += 1),
+        }
+        handles
+    }
+
+    /// Deferred disk read for `key`, when the backend supports one; see
+    /// [`TextureCacheStore::pixel_loader`].
+    ///
+    /// The loader counts a disk hit or a miss in
+    /// [`stats`](TextureCache::stats) when it runs.
+    pub fn pixel_loader(&self, key: &TextureCacheKey) -> Option<PixelLoader> {
+        let load = self.inner.lock().ok()?.pixel_loader(key)?;
+        let stats = Arc::clone(&self.stats);
+        let kind = key.kind;
+        Some(Box::new(move || {
+            let map = load();
+            record(&stats, kind, |stats| match map {
+                Some(_) => stats.disk_hits += 1,
+                None => stats.misses += 1,
+            });
+            map
+        }))
     }
 
     /// Look up a key without touching `Assets<Image>`.
@@ -210,8 +320,15 @@ impl TextureCache {
     /// return hits here; disk-backed stores need an image upload and return
     /// `None`.  Prefer [`get`](TextureCache::get) whenever an
     /// `Assets<Image>` is on hand.
+    ///
+    /// Counts a hit or a miss in [`stats`](TextureCache::stats).
     pub fn get_handles(&self, key: &TextureCacheKey) -> Option<Arc<GeneratedHandles>> {
-        self.inner.lock().ok()?.peek_memory_only(key)
+        let handles = self.inner.lock().ok()?.peek_memory_only(key);
+        match handles {
+            Some(_) => self.record(key.kind, |kind| kind.hits += 1),
+            None => self.record(key.kind, |kind| kind.misses += 1),
+        }
+        handles
     }
 
     /// Persist raw pixels for `key` ahead of the upload that consumes them.
@@ -229,18 +346,229 @@ impl TextureCache {
             store.put(key, handles, false, None);
         }
     }
+
+    /// Snapshot of the cache's counters since creation (or the last
+    /// [`reset_stats`](TextureCache::reset_stats)), completed with the
+    /// store's evictions and resident bytes.
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = self.stats.lock().map(|s| s.clone()).unwrap_or_default();
+        if let Ok(store) = self.inner.lock() {
+            store.report_usage(&mut stats);
+        }
+        stats
+    }
+
+    /// Zero the hit, miss and generation-time counters.  Store-side figures
+    /// (evictions, resident bytes) are not reset.
+    pub fn reset_stats(&self) {
+        if let Ok(mut stats) = self.stats.lock() {
+            *stats = CacheStats::default();
+        }
+    }
+
+    /// Count a generator run of `elapsed` wall time.  The miss that led to
+    /// it was counted by the lookup.
+    pub(crate) fn record_generation(&self, kind: &'static str, elapsed: Duration) {
+        self.record(kind, |stats| {
+            stats.generations += 1;
+            stats.generation_time += elapsed;
+        });
+    }
+
+    fn record(&self, kind: &'static str, update: impl FnOnce(&mut KindStats)) {
+        record(&self.stats, kind, update);
+    }
+}
+
+/// Apply `update` to `kind`'s row of `stats`.
+fn record(stats: &Mutex<CacheStats>, kind: &'static str, update: impl FnOnce(&mut KindStats)) {
+    if let Ok(mut stats) = stats.lock() {
+        update(stats.kinds.entry(kind).or_default());
+    }
+}
+
+/// Per-kind cache counters; one row of [`CacheStats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KindStats {
+    /// Requests served from memory without touching the generator.
+    pub hits: u64,
+    /// Requests served by reading a persisted blob ([`FileStore`]).
+    pub disk_hits: u64,
+    /// Lookups that found nothing: a [`TextureCache::get`] or
+    /// [`get_handles`](TextureCache::get_handles) returning `None`, or a
+    /// deferred disk read coming back empty.  Counted at the lookup, so
+    /// callers that then spawn the generator themselves are included.
+    pub misses: u64,
+    /// Entries the store dropped to stay within its budget.
+    pub evictions: u64,
+    /// Estimated texture memory the store currently holds for this kind.
+    pub bytes_resident: u64,
+    /// Generator runs timed in
+    /// [`generation_time`](KindStats::generation_time) — those dispatched
+    /// by the material builders after a miss.
+    pub generations: u64,
+    /// Wall time of every timed generation, summed.  Measured on the worker
+    /// from the start of the generator to the finished mip chain, so it
+    /// excludes queueing and the main-thread upload.
+    pub generation_time: Duration,
+}
+
+impl KindStats {
+    /// Fraction of requests served without generating, in `[0, 1]`; `0`
+    /// before the first request.
+    pub fn hit_ratio(&self) -> f64 {
+        let served = self.hits + self.disk_hits;
+        let requests = served + self.misses;
+        if requests == 0 {
+            0.0
+        } else {
+            served as f64 / requests as f64
+        }
+    }
+
+    /// Mean wall time per timed generation; zero before the first.
+    pub fn mean_generation_time(&self) -> Duration {
+        u32::try_from(self.generations)
+            .ok()
+            .and_then(|generations| self.generation_time.checked_div(generations))
+            .unwrap_or_default()
+    }
+
+    fn add(&mut self, other: &KindStats) {
+        self.hits += other.hits;
+        self.disk_hits += other.disk_hits;
+        self.misses += other.misses;
+        self.generations += other.generations;
+        self.evictions += other.evictions;
+        self.bytes_resident += other.bytes_resident;
+        self.generation_time += other.generation_time;
+    }
+}
+
+/// Snapshot returned by [`TextureCache::stats`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    /// Counters per [`TextureCacheKey::kind`], in name order.
+    pub kinds: BTreeMap<&'static str, KindStats>,
+    /// Blobs a [`FileStore`] deleted to stay within its disk budget.  Blob
+    /// file names are hashes, so these are not attributed to a kind.
+    pub disk_evictions: u64,
+}
+
+impl CacheStats {
+    /// Every kind's counters summed.  `evictions` includes
+    /// [`disk_evictions`](CacheStats::disk_evictions).
+    pub fn total(&self) -> KindStats {
+        let mut total = KindStats {
+            evictions: self.disk_evictions,
+            ..KindStats::default()
+        };
+        for kind in self.kinds.values() {
+            total.add(kind);
+        }
+        total
+    }
+}
+
+/// [`bevy::diagnostic`] paths under which
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) publishes the
+/// [`TextureCache`] totals every frame, for the diagnostics overlay and
+/// `LogDiagnosticsPlugin`.  Counters are cumulative since the cache was
+/// created or last [reset](TextureCache::reset_stats); per-kind figures are
+/// on [`TextureCache::stats`].
+pub struct CacheDiagnostics;
+
+impl CacheDiagnostics {
+    /// Memory hits.
+    pub const HITS: DiagnosticPath = DiagnosticPath::const_new("symbios_texture/cache/hits");
+    /// Disk hits.
+    pub const DISK_HITS: DiagnosticPath =
+        DiagnosticPath::const_new("symbios_texture/cache/disk_hits");
+    /// Misses (generator runs).
+    pub const MISSES: DiagnosticPath = DiagnosticPath::const_new("symbios_texture/cache/misses");
+    /// Memory and disk evictions.
+    pub const EVICTIONS: DiagnosticPath =
+        DiagnosticPath::const_new("symbios_texture/cache/evictions");
+    /// Estimated resident texture memory, in bytes.
+    pub const BYTES_RESIDENT: DiagnosticPath =
+        DiagnosticPath::const_new("symbios_texture/cache/bytes_resident");
+    /// Mean generation wall time per miss, in milliseconds.
+    pub const GENERATION_MS: DiagnosticPath =
+        DiagnosticPath::const_new("symbios_texture/cache/generation_ms");
+
+    /// One [`Diagnostic`] per path, ready to register.  History is a single
+    /// sample: the values are running totals, so averaging would only lag.
+    pub fn diagnostics() -> [Diagnostic; 6] {
+        [
+            (Self::HITS, ""),
+            (Self::DISK_HITS, ""),
+            (Self::MISSES, ""),
+            (Self::EVICTIONS, ""),
+            (Self::BYTES_RESIDENT, " B"),
+            (Self::GENERATION_MS, " ms"),
+        ]
+        .map(|(path, suffix)| {
+            Diagnostic::new(path)
+                .with_suffix(suffix)
+                .with_max_history_length(1)
+        })
+    }
+}
+
+/// Bevy system — publishes [`TextureCache::stats`] totals under the
+/// [`CacheDiagnostics`] paths.  No-op without a [`TextureCache`] resource.
+///
+/// Registered automatically by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin), together with the
+/// diagnostics themselves.
+pub fn measure_cache_diagnostics(cache: Option<Res<TextureCache>>, mut diagnostics: Diagnostics) {
+    let Some(cache) = cache else {
+        return;
+    };
+    let total = cache.stats().total();
+    diagnostics.add_measurement(&CacheDiagnostics::HITS, || total.hits as f64);
+    diagnostics.add_measurement(&CacheDiagnostics::DISK_HITS, || total.disk_hits as f64);
+    diagnostics.add_measurement(&CacheDiagnostics::MISSES, || total.misses as f64);
+    diagnostics.add_measurement(&CacheDiagnostics::EVICTIONS, || total.evictions as f64);
+    diagnostics.add_measurement(&CacheDiagnostics::BYTES_RESIDENT, || {
+        total.bytes_resident as f64
+    });
+    diagnostics.add_measurement(&CacheDiagnostics::GENERATION_MS, || {
+        total.mean_generation_time().as_secs_f64() * 1000.0
+    });
 }
 
-/// In-memory cache with bounded capacity and FIFO eviction.
+/// In-memory cache with bounded capacity and LRU eviction.
 ///
-/// Eviction is FIFO on insertion order — simpler than full LRU and adequate
-/// for the typical access pattern (palettes loaded in bulk, hits clustered
-/// around hot configs).  When the cap is reached the oldest entry is
-/// dropped; re-inserting an existing key updates in place without evicting.
+/// Every [`get`](TextureCacheStore::get) or
+/// [`peek_memory_only`](TextureCacheStore::peek_memory_only) hit marks the
+/// entry as used.  When an insert pushes the store past `max_entries` — or
+/// past the optional byte budget — the least recently used entries are
+/// dropped until it fits again.  Re-inserting an existing key updates it in
+/// place and counts as a use.
+///
+/// Entry sizes are estimated from the key's dimensions: `width × height`
+/// texels times 4 bytes per RGBA8 map (albedo, normal, ORM and emissive when
+/// present) plus 2 for an `R16Float` height map, grown by a third for the
+/// mip chain.  That tracks the GPU footprint of the uploaded images without
+/// touching `Assets<Image>`.
 pub struct MemoryStore {
     max_entries: usize,
-    entries: HashMap<TextureCacheKey, Arc<GeneratedHandles>>,
-    insertion_order: VecDeque<TextureCacheKey>,
+    max_bytes: Option<u64>,
+    total_bytes: u64,
+    /// Monotonic use counter; an entry's `last_used` is the tick of its
+    /// latest hit or insert.  Atomic so `peek_memory_only(&self)` can touch.
+    clock: AtomicU64,
+    entries: HashMap<TextureCacheKey, MemoryEntry>,
+    /// Entries dropped by [`evict`](MemoryStore::evict), per kind.
+    evictions: HashMap<&'static str, u64>,
+}
+
+/// One [`MemoryStore`] slot.
+struct MemoryEntry {
+    handles: Arc<GeneratedHandles>,
+    bytes: u64,
+    last_used: AtomicU64,
 }
 
 impl MemoryStore {
@@ -250,19 +578,95 @@ impl MemoryStore {
         let cap = max_entries.max(1);
         Self {
             max_entries: cap,
+            max_bytes: None,
+            total_bytes: 0,
+            clock: AtomicU64::new(0),
             entries: HashMap::with_capacity(cap),
-            insertion_order: VecDeque::with_capacity(cap),
+            evictions: HashMap::new(),
+        }
+    }
+
+    /// Build a memory store bounded by `max_entries` and by `max_bytes` of
+    /// estimated texture memory, whichever is hit first.
+    ///
+    /// An entry larger than the whole budget is still kept (as the only
+    /// entry) — it was just generated, so dropping it would only force a
+    /// regeneration on the next request.
+    pub fn with_byte_budget(max_entries: usize, max_bytes: u64) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            ..Self::new(max_entries)
+        }
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` when nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Estimated texture memory of every cached entry, in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Look `key` up and mark it as used.
+    fn touch(&self, key: &TextureCacheKey) -> Option<Arc<GeneratedHandles>> {
+        let entry = self.entries.get(key)?;
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(entry.handles.clone())
+    }
+
+    fn over_budget(&self) -> bool {
+        self.entries.len() > self.max_entries
+            || self.max_bytes.is_some_and(|max| self.total_bytes > max)
+    }
+
+    /// Drop least recently used entries other than `keep` until both caps
+    /// hold.
+    fn evict(&mut self, keep: &TextureCacheKey) {
+        while self.over_budget() {
+            let Some(victim) = self
+                .entries
+                .iter()
+                .filter(|(key, _)| *key != keep)
+                .min_by_key(|(_, entry)| entry.last_used.load(Ordering::Relaxed))
+                .map(|(key, _)| key.clone())
+            else {
+                return;
+            };
+            if let Some(entry) = self.entries.remove(&victim) {
+                self.total_bytes -= entry.bytes;
+                *self.evictions.entry(victim.kind).or_default() += 1;
+            }
         }
     }
 }
 
+/// Estimated GPU footprint of `handles` uploaded at `key`'s resolution; see
+/// [`MemoryStore`].
+fn estimated_bytes(key: &TextureCacheKey, handles: &GeneratedHandles) -> u64 {
+    let texels = key.width as u64 * key.height as u64;
+    let rgba_maps = 3 + handles.emissive.is_some() as u64;
+    let per_texel = rgba_maps * 4 + handles.height.is_some() as u64 * 2;
+    texels * per_texel * 4 / 3
+}
+
 impl TextureCacheStore for MemoryStore {
     fn get(
         &mut self,
         key: &TextureCacheKey,
         _images: &mut Assets<Image>,
     ) -> Option<Arc<GeneratedHandles>> {
-        self.entries.get(key).cloned()
+        self.touch(key)
     }
 
     fn put(
@@ -272,23 +676,30 @@ impl TextureCacheStore for MemoryStore {
         _is_card: bool,
         _map: Option<&TextureMap>,
     ) {
-        // Replace path: keep insertion order untouched, just refresh the value.
-        if let std::collections::hash_map::Entry::Occupied(mut e) = self.entries.entry(key.clone())
-        {
-            e.insert(handles);
-            return;
-        }
-        if self.entries.len() >= self.max_entries
-            && let Some(oldest) = self.insertion_order.pop_front()
-        {
-            self.entries.remove(&oldest);
+        let bytes = estimated_bytes(&key, &handles);
+        let entry = MemoryEntry {
+            handles,
+            bytes,
+            last_used: AtomicU64::new(self.tick()),
+        };
+        if let Some(previous) = self.entries.insert(key.clone(), entry) {
+            self.total_bytes -= previous.bytes;
         }
-        self.insertion_order.push_back(key.clone());
-        self.entries.insert(key, handles);
+        self.total_bytes += bytes;
+        self.evict(&key);
     }
 
     fn peek_memory_only(&self, key: &TextureCacheKey) -> Option<Arc<GeneratedHandles>> {
-        self.entries.get(key).cloned()
+        self.touch(key)
+    }
+
+    fn report_usage(&self, stats: &mut CacheStats) {
+        for (key, entry) in &self.entries {
+            stats.kinds.entry(key.kind).or_default().bytes_resident += entry.bytes;
+        }
+        for (&kind, &evictions) in &self.evictions {
+            stats.kinds.entry(kind).or_default().evictions += evictions;
+        }
     }
 }
 
@@ -299,19 +710,301 @@ impl TextureCacheStore for MemoryStore {
 /// version:      u32 LE         (FILE_FORMAT_VERSION)
 /// manifest:     u32 LE         (manifest_version the blob was written under)
 /// is_card:      u8
+/// compression:  u8             (BlobCompression; absent in version 3)
 /// width:        u32 LE
 /// height:       u32 LE
-/// albedo_len:   u32 LE
+/// albedo_len:   u32 LE         (stored length, i.e. after compression)
 /// normal_len:   u32 LE
 /// roughness_len:u32 LE
 /// emissive_len: u32 LE         (0 = no emissive map)
+/// checksum:     u64 LE         (FNV-1a of every other byte; absent before version 5)
 /// albedo:       albedo_len bytes
 /// normal:       normal_len bytes
 /// roughness:    roughness_len bytes
 /// emissive:     emissive_len bytes
 /// ```
+///
+/// Version 3 blobs (raw maps, no compression byte) and version 4 blobs (no
+/// checksum) are still read.
 const FILE_MAGIC: &[u8; 4] = b"BSTX";
-const FILE_FORMAT_VERSION: u32 = 3;
+const FILE_FORMAT_VERSION: u32 = 5;
+/// Oldest blob format version [`FileStore`] still reads.
+const OLDEST_READABLE_FORMAT_VERSION: u32 = 3;
+/// First format version with a compression byte.
+const COMPRESSION_FORMAT_VERSION: u32 = 4;
+/// First format version with a checksum.
+const CHECKSUM_FORMAT_VERSION: u32 = 5;
+
+/// `true` for blob format versions [`FileStore`] can read.
+fn is_readable_version(version: u32) -> bool {
+    (OLDEST_READABLE_FORMAT_VERSION..=FILE_FORMAT_VERSION).contains(&version)
+}
+
+/// Why a blob file could not be served.
+enum BlobError {
+    /// Not this store's blob: wrong magic, unreadable format version or
+    /// another manifest version.  Left alone on disk.
+    Foreign,
+    /// Damaged — truncated, failing its checksum, or undecodable.
+    Corrupt(&'static str),
+}
+
+/// Cursor over a blob's bytes; every read reports truncation as corruption.
+struct BlobReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BlobReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BlobError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(BlobError::Corrupt("truncated"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, BlobError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BlobError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, BlobError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// FNV-1a over `parts`, in order — the blob checksum.
+fn blob_checksum(parts: &[&[u8]]) -> u64 {
+    use std::hash::Hasher;
+    let mut h = symbios_texture::fingerprint::Fnv1a::new();
+    for part in parts {
+        h.write(part);
+    }
+    h.finish()
+}
+
+/// Decode a blob written under `manifest_version` into its card flag and
+/// base-level map.
+fn parse_blob(bytes: &[u8], manifest_version: u32) -> Result<(bool, TextureMap), BlobError> {
+    let mut reader = BlobReader { bytes, pos: 0 };
+    let foreign = |_| BlobError::Foreign;
+    if reader.take(4).map_err(foreign)? != FILE_MAGIC {
+        return Err(BlobError::Foreign);
+    }
+    let version = reader.u32().map_err(foreign)?;
+    if !is_readable_version(version) {
+        return Err(BlobError::Foreign);
+    }
+    // The filename hash already encodes the manifest version; validating
+    // the header copy too guards against hash collisions and hand-moved
+    // files.
+    if reader.u32()? != manifest_version {
+        return Err(BlobError::Foreign);
+    }
+    let is_card = reader.u8()? != 0;
+    let compression = if version >= COMPRESSION_FORMAT_VERSION {
+        BlobCompression::from_tag(reader.u8()?).ok_or(BlobError::Corrupt("unknown compression"))?
+    } else {
+        BlobCompression::None
+    };
+    let [
+        width,
+        height,
+        albedo_len,
+        normal_len,
+        roughness_len,
+        emissive_len,
+    ] = [(); 6].map(|_| reader.u32());
+    let (width, height) = (width?, height?);
+    if version >= CHECKSUM_FORMAT_VERSION {
+        let checksum_at = reader.pos;
+        let stored = reader.u64()?;
+        if blob_checksum(&[&bytes[..checksum_at], &bytes[reader.pos..]]) != stored {
+            return Err(BlobError::Corrupt("checksum mismatch"));
+        }
+    }
+
+    let base = width as usize * height as usize * 4;
+    let mut read_map = |stored_len: u32, filter| {
+        let stored = reader.take(stored_len as usize)?;
+        let decoded = match compression {
+            BlobCompression::None => (stored.len() == base).then(|| stored.to_vec()),
+            BlobCompression::Deflate => decompress_map(stored, base, width as usize, filter),
+        };
+        decoded.ok_or(BlobError::Corrupt("undecodable pixels"))
+    };
+    let albedo = read_map(albedo_len?, PlaneFilter::Colour)?;
+    let normal = read_map(normal_len?, PlaneFilter::Normal)?;
+    let roughness = read_map(roughness_len?, PlaneFilter::Linear)?;
+    let emissive = match emissive_len? {
+        0 => None,
+        len => Some(read_map(len, PlaneFilter::Colour)?),
+    };
+    if reader.pos != bytes.len() {
+        return Err(BlobError::Corrupt("trailing bytes"));
+    }
+
+    let map = TextureMap {
+        albedo,
+        normal,
+        roughness,
+        emissive,
+        width,
+        height,
+        mip_level_count: 1,
+    };
+    Ok((is_card, map))
+}
+
+/// Write `bytes` to `path` through a uniquely named temporary sibling and a
+/// rename, so a crash mid-write leaves the previous file (or none), never a
+/// partial one.  Not fsynced — a power loss can still lose the rename, which
+/// the blob checksum then catches.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+    let tmp = path.with_extension(format!(
+        "{}-{}.{TMP_EXTENSION}",
+        std::process::id(),
+        NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = fs::write(&tmp, bytes).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Extension of [`write_atomic`]'s temporary files.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Age after which [`FileStore::prune_stale_manifests`] treats a temporary
+/// file as left behind by a crashed writer.
+const STALE_TMP_AGE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How [`FileStore`] encodes the pixel maps of the blobs it writes.
+///
+/// Blobs record their encoding, so a store reads every kind regardless of
+/// its own setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlobCompression {
+    /// Raw RGBA8 maps — the fastest to write and read.
+    #[default]
+    None,
+    /// Lossless: each map is split into channel planes, run through a
+    /// prediction filter chosen per map type (green-difference for colour,
+    /// Z-from-XY for normals, plain for ORM), delta-coded and deflated.
+    /// Typically a few times smaller than [`None`](BlobCompression::None),
+    /// for some CPU on every write and first hit.
+    Deflate,
+}
+
+impl BlobCompression {
+    fn tag(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Deflate => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::None),
+            1 => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// Per-map prediction used by [`BlobCompression::Deflate`].
+#[derive(Clone, Copy)]
+enum PlaneFilter {
+    /// sRGB colour (albedo, emissive): store G, R − G, B − G, A — colour
+    /// channels move together, so the differences are small.
+    Colour,
+    /// Tangent-space normals: store X, Y, Z minus the Z reconstructed from
+    /// X and Y (nearly always 0 or ±1), A.
+    Normal,
+    /// Linear data (ORM): channels are independent; store as-is.
+    Linear,
+}
+
+/// Z byte of a unit normal with the given encoded X and Y bytes.
+fn predict_normal_z(x: u8, y: u8) -> u8 {
+    let decode = |c: u8| c as f32 / 127.5 - 1.0;
+    let (nx, ny) = (decode(x), decode(y));
+    let nz = (1.0 - nx * nx - ny * ny).max(0.0).sqrt();
+    ((nz + 1.0) * 127.5).round() as u8
+}
+
+/// Filter an RGBA8 map of `width`-texel rows into four delta-coded channel
+/// planes and deflate them.
+fn compress_map(rgba: &[u8], width: usize, filter: PlaneFilter) -> Vec<u8> {
+    let texels = rgba.len() / 4;
+    let mut planes = vec![0u8; rgba.len()];
+    for (i, px) in rgba.chunks_exact(4).enumerate() {
+        let [r, g, b, a] = [px[0], px[1], px[2], px[3]];
+        let filtered = match filter {
+            PlaneFilter::Colour => [g, r.wrapping_sub(g), b.wrapping_sub(g), a],
+            PlaneFilter::Normal => [r, g, b.wrapping_sub(predict_normal_z(r, g)), a],
+            PlaneFilter::Linear => [r, g, b, a],
+        };
+        for (c, v) in filtered.into_iter().enumerate() {
+            planes[c * texels + i] = v;
+        }
+    }
+    // Delta from the left neighbour, or from the texel above at the start
+    // of a row.  Walk backwards so every prediction still sees its
+    // unfiltered source.
+    for plane in planes.chunks_exact_mut(texels.max(1)) {
+        for i in (1..plane.len()).rev() {
+            let prediction = if i % width == 0 {
+                plane[i - width]
+            } else {
+                plane[i - 1]
+            };
+            plane[i] = plane[i].wrapping_sub(prediction);
+        }
+    }
+    miniz_oxide::deflate::compress_to_vec(&planes, 6)
+}
+
+/// Inverse of [`compress_map`]; `None` unless the stream inflates to
+/// exactly `len` bytes.
+fn decompress_map(stored: &[u8], len: usize, width: usize, filter: PlaneFilter) -> Option<Vec<u8>> {
+    let mut planes = miniz_oxide::inflate::decompress_to_vec_with_limit(stored, len).ok()?;
+    if planes.len() != len || width == 0 {
+        return None;
+    }
+    let texels = len / 4;
+    for plane in planes.chunks_exact_mut(texels.max(1)) {
+        for i in 1..plane.len() {
+            let prediction = if i % width == 0 {
+                plane[i - width]
+            } else {
+                plane[i - 1]
+            };
+            plane[i] = plane[i].wrapping_add(prediction);
+        }
+    }
+    let mut rgba = vec![0u8; len];
+    for (i, px) in rgba.chunks_exact_mut(4).enumerate() {
+        let [f0, f1, f2, f3] = [0, 1, 2, 3].map(|c| planes[c * texels + i]);
+        let unfiltered = match filter {
+            PlaneFilter::Colour => [f1.wrapping_add(f0), f0, f2.wrapping_add(f0), f3],
+            PlaneFilter::Normal => [f0, f1, f2.wrapping_add(predict_normal_z(f0, f1)), f3],
+            PlaneFilter::Linear => [f0, f1, f2, f3],
+        };
+        px.copy_from_slice(&unfiltered);
+    }
+    Some(rgba)
+}
 
 /// Disk-backed cache.  Each entry is a single binary blob in `dir`.
 ///
@@ -323,10 +1016,209 @@ const FILE_FORMAT_VERSION: u32 = 3;
 /// directory.  Entries from older crate versions may be unreadable when the
 /// on-disk format version (`FILE_FORMAT_VERSION` in the blob header)
 /// changes; the loader skips entries that fail magic / version checks, so
-/// stale files are inert rather than fatal.
+/// stale files are inert rather than fatal, and
+/// [`prune_stale_manifests`](FileStore::prune_stale_manifests) deletes them.
+///
+/// # Disk budget
+///
+/// The store keeps an access-time index (`index.lru`) beside the blobs:
+/// every write and hit stamps the blob's entry.  With a budget set
+/// ([`with_disk_budget`](FileStore::with_disk_budget)), a write that pushes
+/// the directory past it deletes the least recently used blobs until it
+/// fits.  The index is saved after pruning and when the store is dropped;
+/// blobs it does not know about (e.g. after a crash) are ranked by their
+/// modification time.
 pub struct FileStore {
     root: PathBuf,
     manifest_version: u32,
+    max_bytes: Option<u64>,
+    compression: BlobCompression,
+    /// Shared with in-flight [`PixelLoader`]s, which stamp hits and drop
+    /// corrupt blobs from their worker thread.
+    index: Arc<Mutex<FileIndex>>,
+}
+
+/// File name of the access-time index inside a [`FileStore`] directory.
+const INDEX_FILE: &str = "index.lru";
+
+/// Bytes reclaimed by a [`FileStore`] pruning pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruneSummary {
+    /// Blob files deleted.
+    pub files_removed: usize,
+    /// Total size of the deleted files.
+    pub bytes_reclaimed: u64,
+}
+
+impl std::fmt::Display for PruneSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "removed {} blob(s), reclaimed {} bytes",
+            self.files_removed, self.bytes_reclaimed
+        )
+    }
+}
+
+/// Size and last-access stamp of every blob in a [`FileStore`] directory.
+#[derive(Default)]
+struct FileIndex {
+    /// Blob file name → entry.
+    entries: HashMap<String, IndexEntry>,
+    /// Latest stamp handed out; stamps are strictly increasing so accesses
+    /// within one clock tick still order.
+    clock: u64,
+    /// Stamps changed since the index file was last written.
+    dirty: bool,
+    /// Blobs deleted by [`prune_to`](FileIndex::prune_to) since the store
+    /// was opened.
+    evictions: u64,
+}
+
+#[derive(Clone, Copy)]
+struct IndexEntry {
+    bytes: u64,
+    /// Milliseconds since the Unix epoch (or later, see [`FileIndex::clock`]).
+    last_access: u64,
+}
+
+/// Milliseconds since the Unix epoch, `0` for clocks set before it.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// `true` for blob files (`<hash>.bin`), as opposed to the index.
+fn is_blob(name: &str) -> bool {
+    name.ends_with(".bin")
+}
+
+impl FileIndex {
+    /// Read `root`'s index file and reconcile it with the blobs actually on
+    /// disk: sizes come from the file system, blobs missing from the index
+    /// are stamped with their modification time, and index lines whose
+    /// blob is gone are dropped.  A missing or unreadable index file is
+    /// treated as empty.
+    fn load(root: &Path) -> std::io::Result<Self> {
+        let recorded: HashMap<String, u64> = fs::read_to_string(root.join(INDEX_FILE))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (name, stamp) = line.split_once(' ')?;
+                Some((name.to_owned(), stamp.parse().ok()?))
+            })
+            .collect();
+
+        let mut index = Self::default();
+        for dir_entry in fs::read_dir(root)? {
+            let dir_entry = dir_entry?;
+            let Ok(name) = dir_entry.file_name().into_string() else {
+                continue;
+            };
+            let metadata = dir_entry.metadata()?;
+            if !is_blob(&name) || !metadata.is_file() {
+                continue;
+            }
+            let last_access = recorded
+                .get(&name)
+                .copied()
+                .unwrap_or_else(|| metadata.modified().map_or(0, unix_millis));
+            index.clock = index.clock.max(last_access);
+            index.entries.insert(
+                name,
+                IndexEntry {
+                    bytes: metadata.len(),
+                    last_access,
+                },
+            );
+        }
+        index.dirty = index.entries.len() != recorded.len();
+        Ok(index)
+    }
+
+    fn save(&mut self, root: &Path) -> std::io::Result<()> {
+        let mut body = String::new();
+        for (name, entry) in &self.entries {
+            body.push_str(&format!("{name} {}\n", entry.last_access));
+        }
+        write_atomic(&root.join(INDEX_FILE), body.as_bytes())?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn stamp(&mut self) -> u64 {
+        self.clock = unix_millis(SystemTime::now()).max(self.clock + 1);
+        self.dirty = true;
+        self.clock
+    }
+
+    /// Record a hit on `name`.
+    fn touch(&mut self, name: &str) {
+        let stamp = self.stamp();
+        if let Some(entry) = self.entries.get_mut(name) {
+            entry.last_access = stamp;
+        }
+    }
+
+    /// Record that `name` was (re)written with `bytes` bytes.
+    fn record(&mut self, name: String, bytes: u64) {
+        let last_access = self.stamp();
+        self.entries.insert(name, IndexEntry { bytes, last_access });
+    }
+
+    fn total_bytes(&self) -> u64 {
+        self.entries.values().map(|entry| entry.bytes).sum()
+    }
+
+    /// Delete blob `name` from `root` and the index, adding it to `summary`.
+    /// A blob already gone counts as removed with no bytes reclaimed.
+    fn remove(
+        &mut self,
+        root: &Path,
+        name: &str,
+        summary: &mut PruneSummary,
+    ) -> std::io::Result<()> {
+        match fs::remove_file(root.join(name)) {
+            Ok(()) => {
+                summary.files_removed += 1;
+                summary.bytes_reclaimed += self.entries.get(name).map_or(0, |entry| entry.bytes);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        self.entries.remove(name);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Delete least recently used blobs other than `keep` until the total
+    /// is at most `max_bytes`.
+    fn prune_to(
+        &mut self,
+        root: &Path,
+        max_bytes: u64,
+        keep: Option<&str>,
+    ) -> std::io::Result<PruneSummary> {
+        let mut by_age: Vec<(u64, String)> = self
+            .entries
+            .iter()
+            .filter(|(name, _)| Some(name.as_str()) != keep)
+            .map(|(name, entry)| (entry.last_access, name.clone()))
+            .collect();
+        by_age.sort_unstable();
+
+        let mut summary = PruneSummary::default();
+        let mut total = self.total_bytes();
+        for (_, name) in by_age {
+            if total <= max_bytes {
+                break;
+            }
+            total -= self.entries.get(&name).map_or(0, |entry| entry.bytes);
+            self.remove(root, &name, &mut summary)?;
+        }
+        self.evictions += summary.files_removed as u64;
+        Ok(summary)
+    }
 }
 
 impl FileStore {
@@ -348,55 +1240,192 @@ impl FileStore {
     /// change without a config-field change.
     pub fn with_manifest_version(root: PathBuf, manifest_version: u32) -> std::io::Result<Self> {
         fs::create_dir_all(&root)?;
+        let index = FileIndex::load(&root)?;
         Ok(Self {
             root,
             manifest_version,
+            max_bytes: None,
+            compression: BlobCompression::None,
+            index: Arc::new(Mutex::new(index)),
         })
     }
 
-    fn path_for(&self, key: &TextureCacheKey) -> PathBuf {
+    /// Encode blobs written from now on with `compression`.  Existing blobs
+    /// stay readable whatever their encoding.
+    pub fn with_compression(mut self, compression: BlobCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Cap the directory at `max_bytes` of blobs, pruning least recently
+    /// used ones whenever a write exceeds it.
+    ///
+    /// The budget covers every blob in the directory, including those
+    /// written under other manifest versions.  Call [`prune`](FileStore::prune)
+    /// to enforce it immediately on an existing directory.
+    pub fn with_disk_budget(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Total size of the blobs in the directory, in bytes.
+    pub fn disk_usage(&self) -> u64 {
+        self.lock_index().total_bytes()
+    }
+
+    /// Delete least recently used blobs until the directory fits the disk
+    /// budget.  No-op without one.
+    pub fn prune(&self) -> std::io::Result<PruneSummary> {
+        let Some(max_bytes) = self.max_bytes else {
+            return Ok(PruneSummary::default());
+        };
+        let mut index = self.lock_index();
+        let summary = index.prune_to(&self.root, max_bytes, None)?;
+        index.save(&self.root)?;
+        Ok(summary)
+    }
+
+    /// Delete every blob this store can never read: written under a
+    /// different `manifest_version` or an unreadable on-disk format
+    /// version, or without a valid header at all.  Temporary files left by
+    /// writers that crashed over an hour ago go too.
+    ///
+    /// Run it after bumping `manifest_version` to reclaim the rotated-out
+    /// cache.  Other stores sharing the directory under other manifest
+    /// versions lose their entries too.
+    pub fn prune_stale_manifests(&self) -> std::io::Result<PruneSummary> {
+        let mut index = self.lock_index();
+        let names: Vec<String> = index.entries.keys().cloned().collect();
+        let mut summary = PruneSummary::default();
+        for name in names {
+            let mut header = [0u8; 12];
+            let readable = fs::File::open(self.root.join(&name))
+                .and_then(|mut file| file.read_exact(&mut header))
+                .is_ok();
+            let current = readable
+                && &header[0..4] == FILE_MAGIC
+                && is_readable_version(u32::from_le_bytes(header[4..8].try_into().unwrap()))
+                && header[8..12] == self.manifest_version.to_le_bytes();
+            if !current {
+                index.remove(&self.root, &name, &mut summary)?;
+            }
+        }
+        for dir_entry in fs::read_dir(&self.root)? {
+            let dir_entry = dir_entry?;
+            let path = dir_entry.path();
+            let metadata = dir_entry.metadata()?;
+            let abandoned = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > STALE_TMP_AGE);
+            if path.extension().is_some_and(|ext| ext == TMP_EXTENSION)
+                && abandoned
+                && fs::remove_file(&path).is_ok()
+            {
+                summary.files_removed += 1;
+                summary.bytes_reclaimed += metadata.len();
+            }
+        }
+        index.save(&self.root)?;
+        Ok(summary)
+    }
+
+    fn lock_index(&self) -> std::sync::MutexGuard<'_, FileIndex> {
+        lock_index(&self.index)
+    }
+
+    fn file_name(&self, key: &TextureCacheKey) -> String {
         use std::hash::{DefaultHasher, Hash, Hasher};
         let mut h = DefaultHasher::new();
         self.manifest_version.hash(&mut h);
         key.hash(&mut h);
-        self.root.join(format!("{:016x}.bin", h.finish()))
+        format!("{:016x}.bin", h.finish())
     }
 
-    /// Serialise `map` into the blob file for `key` (see the layout above).
-    /// I/O failures are logged and swallowed — a broken cache write must not
-    /// fail texture generation.
-    fn write_blob(&self, key: &TextureCacheKey, map: &TextureMap, is_card: bool) {
+    fn path_for(&self, key: &TextureCacheKey) -> PathBuf {
+        self.root.join(self.file_name(key))
+    }
+
+    /// Serialise `map` into the blob file for `key`, reporting I/O failures.
+    ///
+    /// The [`TextureCacheStore`] methods route through this but log and
+    /// swallow errors — a broken cache write must not fail texture
+    /// generation.  Offline tools that warm the cache (`symbios-bake`) call
+    /// it directly so a failed write is an error, not a silent cold start.
+    pub fn write_entry(
+        &self,
+        key: &TextureCacheKey,
+        map: &TextureMap,
+        is_card: bool,
+    ) -> std::io::Result<()> {
         let path = self.path_for(key);
         // Persist base levels only: maps arriving from async tasks carry
         // their mip chains appended ([`TextureMap::with_mips`]), but mips
         // are cheap to regenerate on upload and would bloat every blob by a
         // third on disk.
         let base = map.base_len();
-        let albedo = &map.albedo[..base];
-        let normal = &map.normal[..base];
-        let roughness = &map.roughness[..base];
-        let emissive = map.emissive.as_deref().map(|e| &e[..base]);
-        if let Err(e) = (|| -> std::io::Result<()> {
-            let mut file = fs::File::create(&path)?;
-            file.write_all(FILE_MAGIC)?;
-            file.write_all(&FILE_FORMAT_VERSION.to_le_bytes())?;
-            file.write_all(&self.manifest_version.to_le_bytes())?;
-            file.write_all(&[is_card as u8])?;
-            file.write_all(&map.width.to_le_bytes())?;
-            file.write_all(&map.height.to_le_bytes())?;
-            file.write_all(&(albedo.len() as u32).to_le_bytes())?;
-            file.write_all(&(normal.len() as u32).to_le_bytes())?;
-            file.write_all(&(roughness.len() as u32).to_le_bytes())?;
-            file.write_all(&(emissive.map_or(0, |e| e.len()) as u32).to_le_bytes())?;
-            file.write_all(albedo)?;
-            file.write_all(normal)?;
-            file.write_all(roughness)?;
-            if let Some(emissive) = emissive {
-                file.write_all(emissive)?;
-            }
-            Ok(())
-        })() {
-            bevy::log::warn!("FileStore write failed for {}: {e}", path.display());
+        let width = map.width as usize;
+        let encode = |data: &[u8], filter| match self.compression {
+            BlobCompression::None => data[..base].to_vec(),
+            BlobCompression::Deflate => compress_map(&data[..base], width, filter),
+        };
+        let albedo = encode(&map.albedo, PlaneFilter::Colour);
+        let normal = encode(&map.normal, PlaneFilter::Normal);
+        let roughness = encode(&map.roughness, PlaneFilter::Linear);
+        let emissive = map
+            .emissive
+            .as_deref()
+            .map(|e| encode(e, PlaneFilter::Colour));
+        let emissive = emissive.unwrap_or_default();
+
+        let mut header = Vec::with_capacity(46);
+        header.extend_from_slice(FILE_MAGIC);
+        header.extend_from_slice(&FILE_FORMAT_VERSION.to_le_bytes());
+        header.extend_from_slice(&self.manifest_version.to_le_bytes());
+        header.extend_from_slice(&[is_card as u8, self.compression.tag()]);
+        for field in [
+            map.width,
+            map.height,
+            albedo.len() as u32,
+            normal.len() as u32,
+            roughness.len() as u32,
+            emissive.len() as u32,
+        ] {
+            header.extend_from_slice(&field.to_le_bytes());
+        }
+        let payload = [&albedo[..], &normal[..], &roughness[..], &emissive[..]];
+        let mut checksummed = vec![&header[..]];
+        checksummed.extend(payload);
+        let checksum = blob_checksum(&checksummed);
+
+        let mut blob = header;
+        blob.extend_from_slice(&checksum.to_le_bytes());
+        for part in payload {
+            blob.extend_from_slice(part);
+        }
+        write_atomic(&path, &blob)?;
+        let bytes = blob.len() as u64;
+
+        let name = self.file_name(key);
+        let mut index = self.lock_index();
+        index.record(name.clone(), bytes);
+        if let Some(max_bytes) = self.max_bytes
+            && index.total_bytes() > max_bytes
+        {
+            index.prune_to(&self.root, max_bytes, Some(&name))?;
+            index.save(&self.root)?;
+        }
+        Ok(())
+    }
+
+    /// [`write_entry`](FileStore::write_entry), logging instead of failing.
+    fn write_blob(&self, key: &TextureCacheKey, map: &TextureMap, is_card: bool) {
+        if let Err(e) = self.write_entry(key, map, is_card) {
+            bevy::log::warn!(
+                "FileStore write failed for {}: {e}",
+                self.path_for(key).display()
+            );
         }
     }
 }
@@ -407,55 +1436,12 @@ impl TextureCacheStore for FileStore {
         key: &TextureCacheKey,
         images: &mut Assets<Image>,
     ) -> Option<Arc<GeneratedHandles>> {
-        let path = self.path_for(key);
-        let mut file = fs::File::open(&path).ok()?;
-        let mut header = [0u8; 4 + 4 + 4 + 1 + 4 + 4 + 4 + 4 + 4 + 4];
-        file.read_exact(&mut header).ok()?;
-        if &header[0..4] != FILE_MAGIC {
-            return None;
-        }
-        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
-        if version != FILE_FORMAT_VERSION {
-            return None;
-        }
-        // The filename hash already encodes the manifest version; validating
-        // the header copy too guards against hash collisions and hand-moved
-        // files.
-        let manifest = u32::from_le_bytes(header[8..12].try_into().unwrap());
-        if manifest != self.manifest_version {
-            return None;
-        }
-        let is_card = header[12] != 0;
-        let width = u32::from_le_bytes(header[13..17].try_into().unwrap());
-        let height = u32::from_le_bytes(header[17..21].try_into().unwrap());
-        let albedo_len = u32::from_le_bytes(header[21..25].try_into().unwrap()) as usize;
-        let normal_len = u32::from_le_bytes(header[25..29].try_into().unwrap()) as usize;
-        let roughness_len = u32::from_le_bytes(header[29..33].try_into().unwrap()) as usize;
-        let emissive_len = u32::from_le_bytes(header[33..37].try_into().unwrap()) as usize;
-
-        let mut albedo = vec![0u8; albedo_len];
-        let mut normal = vec![0u8; normal_len];
-        let mut roughness = vec![0u8; roughness_len];
-        file.read_exact(&mut albedo).ok()?;
-        file.read_exact(&mut normal).ok()?;
-        file.read_exact(&mut roughness).ok()?;
-        let emissive = if emissive_len > 0 {
-            let mut buf = vec![0u8; emissive_len];
-            file.read_exact(&mut buf).ok()?;
-            Some(buf)
-        } else {
-            None
-        };
-
-        let map = TextureMap {
-            albedo,
-            normal,
-            roughness,
-            emissive,
-            width,
-            height,
-            mip_level_count: 1,
-        };
+        let (is_card, map) = load_blob(
+            &self.root,
+            &self.file_name(key),
+            self.manifest_version,
+            &self.index,
+        )?;
         let handles = if is_card {
             map_to_images_card(map, images)
         } else {
@@ -484,6 +1470,75 @@ impl TextureCacheStore for FileStore {
     fn put_pixels(&mut self, key: &TextureCacheKey, map: &TextureMap, is_card: bool) {
         self.write_blob(key, map, is_card);
     }
+
+    fn report_usage(&self, stats: &mut CacheStats) {
+        stats.disk_evictions += self.lock_index().evictions;
+    }
+
+    fn pixel_loader(&self, key: &TextureCacheKey) -> Option<PixelLoader> {
+        let root = self.root.clone();
+        let name = self.file_name(key);
+        let manifest_version = self.manifest_version;
+        let index = Arc::clone(&self.index);
+        Some(Box::new(move || {
+            load_blob(&root, &name, manifest_version, &index).map(|(_, map)| map)
+        }))
+    }
+}
+
+/// Lock a [`FileStore`] index.  The index holds plain data; a panic
+/// mid-update cannot leave it inconsistent enough to matter, so recover from
+/// poisoning.
+fn lock_index(index: &Mutex<FileIndex>) -> std::sync::MutexGuard<'_, FileIndex> {
+    index.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Read and decode blob `name` from `root`, stamping the hit in `index`.
+///
+/// Shared by [`FileStore::get`] and the store's [`PixelLoader`]s, so it may
+/// run on any thread.  A corrupt blob is logged and deleted — serving it
+/// would upload garbage, and deleting it lets the caller's regeneration
+/// write a good copy.
+fn load_blob(
+    root: &Path,
+    name: &str,
+    manifest_version: u32,
+    index: &Mutex<FileIndex>,
+) -> Option<(bool, TextureMap)> {
+    let path = root.join(name);
+    let bytes = fs::read(&path).ok()?;
+    match parse_blob(&bytes, manifest_version) {
+        Ok(parsed) => {
+            lock_index(index).touch(name);
+            Some(parsed)
+        }
+        Err(BlobError::Foreign) => None,
+        Err(BlobError::Corrupt(reason)) => {
+            bevy::log::warn!(
+                "FileStore deleting corrupt blob {} ({reason})",
+                path.display()
+            );
+            let mut summary = PruneSummary::default();
+            if let Err(e) = lock_index(index).remove(root, name, &mut summary) {
+                bevy::log::warn!("FileStore could not delete {}: {e}", path.display());
+            }
+            None
+        }
+    }
+}
+
+impl Drop for FileStore {
+    fn drop(&mut self) {
+        let mut index = self.lock_index();
+        if index.dirty
+            && let Err(e) = index.save(&self.root)
+        {
+            bevy::log::warn!(
+                "FileStore index write failed for {}: {e}",
+                self.root.display()
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -496,6 +1551,7 @@ mod tests {
             normal: Default::default(),
             roughness: Default::default(),
             emissive: None,
+            height: None,
         })
     }
 
@@ -529,6 +1585,44 @@ mod tests {
         assert!(store.peek_memory_only(&key("Bark", 3)).is_some());
     }
 
+    #[test]
+    fn memory_store_evicts_least_recently_used() {
+        let mut store = MemoryStore::new(2);
+        let mut images = Assets::<Image>::default();
+        store.put(key("Bark", 1), dummy_handles(), false, None);
+        store.put(key("Bark", 2), dummy_handles(), false, None);
+        // Touch the older entry so the newer one becomes the LRU victim.
+        assert!(store.get(&key("Bark", 1), &mut images).is_some());
+        store.put(key("Bark", 3), dummy_handles(), false, None);
+        assert!(store.peek_memory_only(&key("Bark", 1)).is_some());
+        assert!(store.peek_memory_only(&key("Bark", 2)).is_none());
+        assert!(store.peek_memory_only(&key("Bark", 3)).is_some());
+    }
+
+    #[test]
+    fn memory_store_honours_byte_budget() {
+        // 64² texels × 3 RGBA8 maps × 4/3 for mips = 64 KiB per entry.
+        let entry = 64 * 64 * 12 * 4 / 3;
+        let mut store = MemoryStore::with_byte_budget(16, entry * 2);
+        for fp in 1..=3 {
+            store.put(key("Bark", fp), dummy_handles(), false, None);
+        }
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.total_bytes(), entry * 2);
+        assert!(store.peek_memory_only(&key("Bark", 1)).is_none());
+
+        // An entry bigger than the whole budget evicts everything else but
+        // is kept itself.
+        let huge = TextureCacheKey {
+            width: 1024,
+            height: 1024,
+            ..key("Rock", 9)
+        };
+        store.put(huge.clone(), dummy_handles(), false, None);
+        assert_eq!(store.len(), 1);
+        assert!(store.peek_memory_only(&huge).is_some());
+    }
+
     #[test]
     fn memory_store_treats_replace_as_no_evict() {
         let mut store = MemoryStore::new(2);
@@ -540,6 +1634,37 @@ mod tests {
         assert!(store.peek_memory_only(&key("Bark", 2)).is_some());
     }
 
+    #[test]
+    fn cache_stats_track_hits_misses_and_evictions_per_kind() {
+        let entry = 64 * 64 * 12 * 4 / 3;
+        let mut cache = TextureCache::memory(2);
+        let mut images = Assets::<Image>::default();
+        cache.insert(key("Bark", 1), dummy_handles());
+        cache.insert(key("Brick", 1), dummy_handles());
+        assert!(cache.get(&key("Bark", 1), &mut images).is_some());
+        assert!(cache.get_handles(&key("Bark", 1)).is_some());
+        assert!(cache.get(&key("Bark", 2), &mut images).is_none());
+        cache.record_generation("Bark", Duration::from_millis(30));
+        cache.record_generation("Bark", Duration::from_millis(10));
+        // Third entry at capacity 2 evicts the least recently used Brick.
+        cache.insert(key("Bark", 2), dummy_handles());
+
+        let stats = cache.stats();
+        let bark = stats.kinds["Bark"];
+        assert_eq!((bark.hits, bark.disk_hits, bark.misses), (2, 0, 1));
+        assert_eq!(bark.generations, 2);
+        assert_eq!(bark.bytes_resident, entry * 2);
+        assert_eq!(bark.mean_generation_time(), Duration::from_millis(20));
+        assert!((bark.hit_ratio() - 2.0 / 3.0).abs() < 1e-9);
+        let brick = stats.kinds["Brick"];
+        assert_eq!((brick.evictions, brick.bytes_resident), (1, 0));
+        assert_eq!(stats.total().evictions, 1);
+
+        cache.reset_stats();
+        let total = cache.stats().total();
+        assert_eq!((total.hits, total.misses, total.evictions), (0, 0, 1));
+    }
+
     fn tiny_map(w: u32, h: u32) -> TextureMap {
         let n = (w * h * 4) as usize;
         TextureMap {
@@ -648,6 +1773,251 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    /// Size on disk of a [`tiny_map`] blob: header plus three RGBA8 maps.
+    fn tiny_blob_len(w: u32, h: u32) -> u64 {
+        46 + 3 * (w * h * 4) as u64
+    }
+
+    #[test]
+    fn file_store_prunes_least_recently_used_over_budget() {
+        let dir = scratch_dir("budget");
+        let blob = tiny_blob_len(4, 4);
+        let mut images = Assets::<Image>::default();
+        {
+            let mut store = FileStore::new(dir.clone())
+                .expect("create store dir")
+                .with_disk_budget(blob * 2);
+            store.put_pixels(&key("Bark", 1), &tiny_map(4, 4), false);
+            store.put_pixels(&key("Bark", 2), &tiny_map(4, 4), false);
+            // A hit makes entry 1 the most recently used.
+            assert!(store.get(&key("Bark", 1), &mut images).is_some());
+        }
+
+        // Access stamps survive a restart through the index.
+        let mut store = FileStore::new(dir.clone())
+            .expect("reopen store dir")
+            .with_disk_budget(blob * 2);
+        assert_eq!(store.disk_usage(), blob * 2);
+        store.put_pixels(&key("Bark", 3), &tiny_map(4, 4), false);
+        assert_eq!(store.disk_usage(), blob * 2);
+        assert!(store.get(&key("Bark", 1), &mut images).is_some());
+        assert!(store.get(&key("Bark", 2), &mut images).is_none());
+        assert!(store.get(&key("Bark", 3), &mut images).is_some());
+        let mut stats = CacheStats::default();
+        store.report_usage(&mut stats);
+        assert_eq!(stats.disk_evictions, 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn prune_stale_manifests_reclaims_rotated_blobs() {
+        let dir = scratch_dir("stale");
+        let k = key("Bark", 5);
+        let mut images = Assets::<Image>::default();
+
+        let mut old = FileStore::with_manifest_version(dir.clone(), 0).expect("create v0");
+        old.put_pixels(&k, &tiny_map(4, 4), false);
+        drop(old);
+        fs::write(dir.join("garbage.bin"), b"nope").expect("write junk blob");
+
+        let mut current = FileStore::with_manifest_version(dir.clone(), 1).expect("open v1");
+        current.put_pixels(&k, &tiny_map(2, 2), false);
+        let summary = current.prune_stale_manifests().expect("prune");
+        assert_eq!(
+            summary,
+            PruneSummary {
+                files_removed: 2,
+                bytes_reclaimed: tiny_blob_len(4, 4) + 4,
+            }
+        );
+        assert!(current.get(&k, &mut images).is_some(), "current entry kept");
+        assert_eq!(current.disk_usage(), tiny_blob_len(2, 2));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    /// A map whose channels all carry structure: albedo with correlated
+    /// colour, unit normals, independent ORM channels.
+    fn textured_map(w: u32, h: u32) -> TextureMap {
+        let mut map = tiny_map(w, h);
+        for i in 0..(w * h) as usize {
+            let (x, y) = ((i as u32 % w) as f32, (i as u32 / w) as f32);
+            let shade = (x * 7.0 + y * 3.0) as u8;
+            map.albedo[i * 4..i * 4 + 4].copy_from_slice(&[shade, shade / 2, shade / 3, 255]);
+            let (nx, ny) = ((x * 0.3).sin() * 0.4, (y * 0.2).cos() * 0.4);
+            let nz = (1.0 - nx * nx - ny * ny).sqrt();
+            let enc = |v: f32| ((v + 1.0) * 127.5).round() as u8;
+            map.normal[i * 4..i * 4 + 4].copy_from_slice(&[enc(nx), enc(ny), enc(nz), 255]);
+            map.roughness[i * 4..i * 4 + 4].copy_from_slice(&[255, (x * 9.0) as u8, 0, 255]);
+        }
+        map.emissive = Some(map.albedo.iter().map(|&v| v / 4).collect());
+        map
+    }
+
+    #[test]
+    fn plane_filters_round_trip_losslessly() {
+        let map = textured_map(13, 7);
+        for (data, filter) in [
+            (&map.albedo, PlaneFilter::Colour),
+            (&map.normal, PlaneFilter::Normal),
+            (&map.roughness, PlaneFilter::Linear),
+        ] {
+            let stored = compress_map(data, 13, filter);
+            assert!(stored.len() < data.len());
+            let restored = decompress_map(&stored, data.len(), 13, filter).expect("inflate");
+            assert_eq!(&restored, data);
+        }
+    }
+
+    #[test]
+    fn compressed_blobs_round_trip_and_shrink() {
+        let dir = scratch_dir("deflate");
+        let k = key("Bark", 51);
+        let map = textured_map(32, 32);
+
+        let mut store = FileStore::new(dir.clone())
+            .expect("create store dir")
+            .with_compression(BlobCompression::Deflate);
+        store.put_pixels(&k, &map, false);
+        assert!(store.disk_usage() < tiny_blob_len(32, 32) / 2);
+
+        // Read back through a store that writes raw blobs: the encoding is
+        // per blob, not per store.
+        let mut raw = FileStore::new(dir.clone()).expect("reopen store dir");
+        let mut images = Assets::<Image>::default();
+        let handles = raw.get(&k, &mut images).expect("hit");
+        let albedo = images.get(&handles.albedo).expect("albedo uploaded");
+        let restored = albedo.data.as_ref().expect("pixels");
+        assert_eq!(&restored[..map.base_len()], &map.albedo[..]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_uncompressed_blobs_stay_readable() {
+        let dir = scratch_dir("legacy");
+        let k = key("Bark", 61);
+        let map = tiny_map(2, 2);
+        let store = FileStore::new(dir.clone()).expect("create store dir");
+
+        // Hand-write a version 3 blob: no compression byte.
+        let mut blob = Vec::new();
+        blob.extend_from_slice(FILE_MAGIC);
+        blob.extend_from_slice(&OLDEST_READABLE_FORMAT_VERSION.to_le_bytes());
+        blob.extend_from_slice(&0u32.to_le_bytes());
+        blob.push(0);
+        for v in [2u32, 2, 16, 16, 16, 0] {
+            blob.extend_from_slice(&v.to_le_bytes());
+        }
+        blob.extend_from_slice(&map.albedo);
+        blob.extend_from_slice(&map.normal);
+        blob.extend_from_slice(&map.roughness);
+        fs::write(store.path_for(&k), blob).expect("write legacy blob");
+
+        let mut store = FileStore::new(dir.clone()).expect("reopen store dir");
+        let mut images = Assets::<Image>::default();
+        assert!(store.get(&k, &mut images).is_some());
+        assert_eq!(
+            store.prune_stale_manifests().expect("prune").files_removed,
+            0
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_blobs_are_deleted_instead_of_served() {
+        let dir = scratch_dir("corrupt");
+        let mut store = FileStore::new(dir.clone()).expect("create store dir");
+        let mut images = Assets::<Image>::default();
+
+        let flipped = key("Bark", 71);
+        store.put_pixels(&flipped, &tiny_map(4, 4), false);
+        let path = store.path_for(&flipped);
+        let mut bytes = fs::read(&path).expect("read blob");
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x10;
+        fs::write(&path, bytes).expect("flip a pixel bit");
+
+        let truncated = key("Bark", 72);
+        store.put_pixels(&truncated, &tiny_map(4, 4), false);
+        let path = store.path_for(&truncated);
+        let bytes = fs::read(&path).expect("read blob");
+        fs::write(&path, &bytes[..bytes.len() / 2]).expect("truncate blob");
+
+        for k in [flipped, truncated] {
+            assert!(store.get(&k, &mut images).is_none(), "corrupt blob served");
+            assert!(!store.path_for(&k).exists(), "corrupt blob kept");
+        }
+        assert_eq!(store.disk_usage(), 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pixel_loader_reads_blobs_off_thread() {
+        let dir = scratch_dir("loader");
+        let mut store = FileStore::new(dir.clone()).expect("create store dir");
+        let (hit, miss) = (key("Bark", 91), key("Bark", 92));
+        store.put_pixels(&hit, &tiny_map(4, 4), false);
+
+        let loader = store.pixel_loader(&hit).expect("FileStore loads lazily");
+        let map = std::thread::spawn(loader)
+            .join()
+            .unwrap()
+            .expect("blob read back");
+        assert_eq!((map.width, map.height, map.mip_level_count), (4, 4, 1));
+        assert_eq!(map.albedo, tiny_map(4, 4).albedo);
+
+        let loader = store.pixel_loader(&miss).expect("FileStore loads lazily");
+        assert!(loader().is_none(), "a missing blob must yield nothing");
+        assert!(MemoryStore::new(4).pixel_loader(&hit).is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    /// Deferred reads count their outcome, and misses are counted at the
+    /// lookup whether or not anything is generated afterwards.
+    #[test]
+    fn lookups_count_misses_without_a_generation() {
+        let dir = scratch_dir("stats");
+        let cache = TextureCache::file(dir.clone(), 0).expect("create store dir");
+        let (hit, miss) = (key("Bark", 93), key("Bark", 94));
+        cache.persist_pixels(&hit, &tiny_map(4, 4), false);
+
+        assert!(cache.pixel_loader(&hit).expect("deferred read")().is_some());
+        assert!(cache.pixel_loader(&miss).expect("deferred read")().is_none());
+        assert!(cache.get_handles(&miss).is_none());
+
+        let bark = cache.stats().kinds["Bark"];
+        assert_eq!((bark.hits, bark.disk_hits, bark.misses), (0, 1, 2));
+        assert_eq!(bark.generations, 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_leave_no_temporary_files() {
+        let dir = scratch_dir("atomic");
+        let mut store = FileStore::new(dir.clone()).expect("create store dir");
+        let k = key("Bark", 81);
+        store.put_pixels(&k, &tiny_map(4, 4), false);
+        // Overwrite in place: the rename replaces the previous blob.
+        store.put_pixels(&k, &tiny_map(2, 2), false);
+        drop(store);
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .expect("read dir")
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), 2, "one blob plus the index: {names:?}");
+        assert!(names.iter().all(|name| !name.ends_with(".tmp")));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn file_store_preserves_card_mode_across_restart() {
         use bevy::image::{ImageAddressMode, ImageSampler};
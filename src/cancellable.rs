@@ -0,0 +1,492 @@
+//! Generators that stop between rows once their task is cancelled, and
+//! report their progress row by row.
+//!
+//! The built-in generators run through the core
+//! [`TextureGenerator::generate`](crate::generator::TextureGenerator::generate),
+//! which takes no cancellation token: once started, a 4096² bark runs to
+//! completion even if its entity is long gone.  Generators written against
+//! this crate can do better.  Implement [`CancellableGenerator`] and spawn
+//! it with [`PendingTexture::cancellable`]; the worker hands it a
+//! [`GenerationContext`] to poll, and the task ends with
+//! [`TextureFailure::Cancelled`] as soon as it gives up.  The same context
+//! feeds the task's [`GenerationProgress`](crate::async_gen::GenerationProgress)
+//! — the fraction of rows done, then a separate
+//! [`Normals`](GenerationStage::Normals) stage.
+//!
+//! A task spawned with
+//! [`PendingTexture::cancellable_with_height`](crate::async_gen::PendingTexture::cancellable_with_height)
+//! also asks its generator for the exact height field
+//! ([`GenerationContext::provide_height`]) instead of reconstructing one
+//! from the normal map.
+//!
+//! Surface generators get the row checks, progress and height for free by sampling their
+//! [`SurfaceCell`] through [`generate_surface_cancellable`], the
+//! counterpart of the core [`generate_surface`](crate::surface::generate_surface):
+//!
+//! ```rust,ignore
+//! struct Stripes;
+//!
+//! impl SurfaceCell for Stripes {
+//!     fn sample(&self, _x: u32, _y: u32, u: f64, _v: f64) -> SurfaceSample {
+//!         let h = (u * std::f64::consts::TAU * 8.0).sin();
+//!         SurfaceSample::matte(h, [0.8, 0.7, 0.5], 0.6)
+//!     }
+//! }
+//!
+//! impl CancellableGenerator for Stripes {
+//!     fn generate_cancellable(
+//!         &self,
+//!         width: u32,
+//!         height: u32,
+//!         ctx: &GenerationContext,
+//!     ) -> Result<TextureMap, TextureFailure> {
+//!         generate_surface_cancellable(width, height, 1.0, self, ctx)
+//!     }
+//! }
+//!
+//! let task = commands.spawn(PendingTexture::cancellable(Stripes, 4096, 4096)).id();
+//!
+//! // Later, in a system with `tasks: Query<&PendingTexture>` — the worker
+//! // stops within a row and the entity gets `TextureFailed(Cancelled)`:
+//! tasks.get(task).unwrap().cancel();
+//! ```
+//!
+//! [`PendingTexture::cancellable`]: crate::async_gen::PendingTexture::cancellable
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use rayon::prelude::*;
+
+use crate::async_gen::{GenerationStage, TaskControl, TextureFailure};
+use crate::generator::{TextureMap, linear_to_srgb, validate_dimensions};
+use crate::height::HeightMap;
+use crate::normal::{BoundaryMode, height_to_normal};
+use crate::surface::SurfaceCell;
+
+/// The running task's side of a
+/// [`PendingTexture`](crate::async_gen::PendingTexture), handed to
+/// [`CancellableGenerator::generate_cancellable`].
+pub struct GenerationContext<'a> {
+    control: &'a TaskControl,
+    /// Rows counted by [`row_finished`](GenerationContext::row_finished).
+    rows_done: AtomicU32,
+    /// Whether the task uploads a height map.
+    wants_height: bool,
+    /// The field handed over by [`provide_height`](GenerationContext::provide_height).
+    height: Mutex<Option<HeightMap>>,
+}
+
+impl<'a> GenerationContext<'a> {
+    pub(crate) fn new(control: &'a TaskControl, wants_height: bool) -> Self {
+        Self {
+            control,
+            rows_done: AtomicU32::new(0),
+            wants_height,
+            height: Mutex::new(None),
+        }
+    }
+
+    /// The field passed to [`provide_height`](GenerationContext::provide_height),
+    /// if any.
+    pub(crate) fn into_height(self) -> Option<HeightMap> {
+        self.height.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// `true` once the task was cancelled — by
+    /// [`PendingTexture::cancel`](crate::async_gen::PendingTexture::cancel)
+    /// or by dropping the component.  Cheap enough to check every row;
+    /// return [`TextureFailure::Cancelled`] when it turns `true`.
+    pub fn is_cancelled(&self) -> bool {
+        self.control.is_cancelled()
+    }
+
+    /// Count one more of `rows` rows as sampled, reporting the running
+    /// share as the [`Pixels`](GenerationStage::Pixels) fraction.  Safe to
+    /// call from parallel row loops.
+    pub fn row_finished(&self, rows: u32) {
+        let done = self.rows_done.fetch_add(1, Ordering::Relaxed) + 1;
+        self.control.report(
+            GenerationStage::Pixels,
+            Some(done as f32 / rows.max(1) as f32),
+        );
+    }
+
+    /// Publish `fraction` of `stage` as done, or `None` when the stage
+    /// cannot measure it — e.g. [`Normals`](GenerationStage::Normals)
+    /// before deriving the normal map.
+    pub fn report(&self, stage: GenerationStage, fraction: Option<f32>) {
+        self.control.report(stage, fraction);
+    }
+
+    /// `true` when the task uploads a height map — see
+    /// [`provide_height`](GenerationContext::provide_height).
+    pub fn wants_height(&self) -> bool {
+        self.wants_height
+    }
+
+    /// Hand over the generator's own height field, built with
+    /// [`HeightMap::from_heights`].  When the task
+    /// [wants one](GenerationContext::wants_height) but none is provided,
+    /// it is reconstructed from the normal map instead.
+    pub fn provide_height(&self, height: HeightMap) {
+        *self.height.lock().unwrap_or_else(|e| e.into_inner()) = Some(height);
+    }
+}
+
+/// A texture generator that can give up part-way through.
+///
+/// The cancellable counterpart of
+/// [`TextureGenerator`](crate::generator::TextureGenerator), spawned with
+/// [`PendingTexture::cancellable`](crate::async_gen::PendingTexture::cancellable)
+/// or [`cancellable_card`](crate::async_gen::PendingTexture::cancellable_card).
+pub trait CancellableGenerator {
+    /// Generate a `width × height` map, checking
+    /// [`ctx.is_cancelled()`](GenerationContext::is_cancelled) between rows
+    /// and failing with [`TextureFailure::Cancelled`] once it is set.
+    /// Report progress through
+    /// [`ctx.row_finished`](GenerationContext::row_finished) and
+    /// [`ctx.report`](GenerationContext::report).
+    fn generate_cancellable(
+        &self,
+        width: u32,
+        height: u32,
+        ctx: &GenerationContext,
+    ) -> Result<TextureMap, TextureFailure>;
+}
+
+/// Render a tileable `width × height` surface through `cell`, skipping the
+/// remaining rows once `ctx` is cancelled.
+///
+/// Reports each sampled row to `ctx`, then the
+/// [`Normals`](GenerationStage::Normals) stage while deriving the normal
+/// map.  When `ctx` [wants a height map](GenerationContext::wants_height),
+/// provides the sampled heights, normalised to `[0, 1]`.
+///
+/// Produces the same map as the core
+/// [`generate_surface`](crate::surface::generate_surface) — sRGB albedo,
+/// ORM, and normals derived from the sampled heights with toroidal
+/// neighbours — with rows sampled in parallel on the ambient pool.
+pub fn generate_surface_cancellable<C: SurfaceCell + Sync>(
+    width: u32,
+    height: u32,
+    normal_strength: f32,
+    cell: &C,
+    ctx: &GenerationContext,
+) -> Result<TextureMap, TextureFailure> {
+    generate_surface_impl(width, height, normal_strength, cell, ctx, false)
+}
+
+/// [`generate_surface_cancellable`] that also collects each sample's
+/// emissive colour into the map's emissive channel, like the core
+/// [`generate_surface_emissive`](crate::surface::generate_surface_emissive).
+pub fn generate_surface_emissive_cancellable<C: SurfaceCell + Sync>(
+    width: u32,
+    height: u32,
+    normal_strength: f32,
+    cell: &C,
+    ctx: &GenerationContext,
+) -> Result<TextureMap, TextureFailure> {
+    generate_surface_impl(width, height, normal_strength, cell, ctx, true)
+}
+
+fn generate_surface_impl<C: SurfaceCell + Sync>(
+    width: u32,
+    height: u32,
+    normal_strength: f32,
+    cell: &C,
+    ctx: &GenerationContext,
+    emit: bool,
+) -> Result<TextureMap, TextureFailure> {
+    validate_dimensions(width, height)?;
+
+    let (w, h) = (width as usize, height as usize);
+    let n = w * h;
+    let mut heights = vec![0.0; n];
+    let mut albedo = vec![0u8; n * 4];
+    let mut roughness = vec![0u8; n * 4];
+    let mut emissive = if emit { vec![0u8; n * 4] } else { Vec::new() };
+
+    let sample_row = |y: usize,
+                      height_row: &mut [f64],
+                      albedo_row: &mut [u8],
+                      orm_row: &mut [u8],
+                      mut emissive_row: Option<&mut [u8]>| {
+        if ctx.is_cancelled() {
+            return;
+        }
+        let v = y as f64 / h as f64;
+        for (x, height_slot) in height_row.iter_mut().enumerate() {
+            let s = cell.sample(x as u32, y as u32, x as f64 / w as f64, v);
+            *height_slot = s.height;
+            let px = x * 4..x * 4 + 4;
+            albedo_row[px.clone()].copy_from_slice(&srgb_pixel(s.color));
+            orm_row[px.clone()].copy_from_slice(&[
+                unorm(s.occlusion),
+                unorm(s.roughness),
+                unorm(s.metallic),
+                255,
+            ]);
+            if let Some(row) = emissive_row.as_deref_mut() {
+                row[px].copy_from_slice(&srgb_pixel(s.emissive));
+            }
+        }
+        ctx.row_finished(height);
+    };
+
+    let rows = heights
+        .par_chunks_mut(w)
+        .zip(albedo.par_chunks_mut(w * 4))
+        .zip(roughness.par_chunks_mut(w * 4))
+        .enumerate();
+    if emit {
+        rows.zip(emissive.par_chunks_mut(w * 4)).for_each(
+            |((y, ((height_row, albedo_row), orm_row)), emissive_row)| {
+                sample_row(y, height_row, albedo_row, orm_row, Some(emissive_row));
+            },
+        );
+    } else {
+        rows.for_each(|(y, ((height_row, albedo_row), orm_row))| {
+            sample_row(y, height_row, albedo_row, orm_row, None);
+        });
+    }
+    if ctx.is_cancelled() {
+        return Err(TextureFailure::Cancelled);
+    }
+
+    ctx.report(GenerationStage::Normals, None);
+    let normal = height_to_normal(&heights, width, height, normal_strength, BoundaryMode::Wrap);
+    if ctx.wants_height() {
+        ctx.provide_height(HeightMap::from_heights(&heights, width, height));
+    }
+    Ok(TextureMap {
+        albedo,
+        normal,
+        roughness,
+        width,
+        height,
+        mip_level_count: 1,
+        emissive: emit.then_some(emissive),
+    })
+}
+
+/// Opaque sRGB-encoded RGBA8 pixel from linear RGB.
+fn srgb_pixel(color: [f32; 3]) -> [u8; 4] {
+    [
+        linear_to_srgb(color[0]),
+        linear_to_srgb(color[1]),
+        linear_to_srgb(color[2]),
+        255,
+    ]
+}
+
+/// `[0, 1]` value as a `u8` channel.
+fn unorm(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::async_gen::{GenerationProgress, PendingTexture};
+    use crate::surface::{SurfaceSample, generate_surface, generate_surface_emissive};
+
+    /// Analytic cell exercising every channel, counting its samples.
+    #[derive(Default)]
+    struct Ripples {
+        samples: AtomicUsize,
+    }
+
+    impl SurfaceCell for Ripples {
+        fn sample(&self, _x: u32, _y: u32, u: f64, v: f64) -> SurfaceSample {
+            self.samples.fetch_add(1, Ordering::Relaxed);
+            let h = (u * std::f64::consts::TAU * 3.0).sin() * (v * std::f64::consts::TAU).cos();
+            SurfaceSample {
+                height: h,
+                color: [u as f32, v as f32, 0.5],
+                roughness: 0.25 + 0.5 * u as f32,
+                metallic: v as f32,
+                occlusion: 0.75,
+                emissive: [v as f32, 0.0, u as f32],
+            }
+        }
+    }
+
+    /// Uncancelled, the driver produces the core driver's map byte for byte.
+    #[test]
+    fn matches_core_surface_driver() {
+        let control = TaskControl::new();
+        let ctx = GenerationContext::new(&control, false);
+        let cell = Ripples::default();
+
+        let ours = generate_surface_cancellable(24, 16, 1.5, &cell, &ctx).unwrap();
+        let core = generate_surface(24, 16, 1.5, None, &cell).unwrap();
+        assert_eq!(ours.albedo, core.albedo);
+        assert_eq!(ours.normal, core.normal);
+        assert_eq!(ours.roughness, core.roughness);
+        assert!(ours.emissive.is_none());
+
+        let ours = generate_surface_emissive_cancellable(24, 16, 1.5, &cell, &ctx).unwrap();
+        let core = generate_surface_emissive(24, 16, 1.5, None, &cell).unwrap();
+        assert_eq!(ours.emissive, core.emissive);
+    }
+
+    /// Every sampled row advances the `Pixels` fraction; the driver ends in
+    /// the separate `Normals` stage.
+    #[test]
+    fn driver_reports_rows_then_normals() {
+        struct Watched<'a> {
+            control: &'a TaskControl,
+            seen: std::sync::Mutex<Vec<GenerationProgress>>,
+        }
+        impl SurfaceCell for Watched<'_> {
+            fn sample(&self, x: u32, _y: u32, u: f64, v: f64) -> SurfaceSample {
+                if x == 0 {
+                    self.seen.lock().unwrap().push(self.control.progress());
+                }
+                SurfaceSample::matte((u + v).sin(), [0.5; 3], 0.5)
+            }
+        }
+
+        let control = TaskControl::new();
+        let cell = Watched {
+            control: &control,
+            seen: std::sync::Mutex::new(Vec::new()),
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            generate_surface_cancellable(8, 4, 1.0, &cell, &GenerationContext::new(&control, false))
+        })
+        .unwrap();
+
+        let fractions: Vec<_> = cell.seen.into_inner().unwrap()[1..]
+            .iter()
+            .map(|progress| {
+                assert_eq!(progress.stage, GenerationStage::Pixels);
+                progress.fraction.unwrap()
+            })
+            .collect();
+        assert_eq!(fractions.len(), 3);
+        for (fraction, expected) in fractions.into_iter().zip([0.25, 0.5, 0.75]) {
+            assert!(
+                (fraction - expected).abs() < 1e-4,
+                "{fraction} != {expected}"
+            );
+        }
+        assert_eq!(
+            control.progress(),
+            GenerationProgress {
+                stage: GenerationStage::Normals,
+                fraction: None,
+            }
+        );
+    }
+
+    /// A task that wants a height map gets the sampled field itself, not a
+    /// reconstruction.
+    #[test]
+    fn driver_provides_the_sampled_heights() {
+        let control = TaskControl::new();
+        let cell = Ripples::default();
+        let ctx = GenerationContext::new(&control, true);
+        generate_surface_cancellable(24, 16, 1.5, &cell, &ctx).unwrap();
+
+        let heights: Vec<f64> = (0..24 * 16)
+            .map(|i| {
+                let (x, y) = (i % 24, i / 24);
+                cell.sample(x, y, x as f64 / 24.0, y as f64 / 16.0).height
+            })
+            .collect();
+        assert_eq!(
+            ctx.into_height(),
+            Some(HeightMap::from_heights(&heights, 24, 16))
+        );
+
+        let ctx = GenerationContext::new(&control, false);
+        generate_surface_cancellable(24, 16, 1.5, &cell, &ctx).unwrap();
+        assert_eq!(ctx.into_height(), None);
+    }
+
+    /// Cancellation stops sampling part-way and reports `Cancelled`.
+    #[test]
+    fn cancelled_driver_stops_between_rows() {
+        struct CancelsAtOrigin<'a> {
+            inner: Ripples,
+            control: &'a TaskControl,
+        }
+        impl SurfaceCell for CancelsAtOrigin<'_> {
+            fn sample(&self, x: u32, y: u32, u: f64, v: f64) -> SurfaceSample {
+                if x == 0 && y == 0 {
+                    self.control.cancel();
+                }
+                self.inner.sample(x, y, u, v)
+            }
+        }
+
+        let control = TaskControl::new();
+        let cell = CancelsAtOrigin {
+            inner: Ripples::default(),
+            control: &control,
+        };
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let result = pool.install(|| {
+            generate_surface_cancellable(
+                64,
+                256,
+                1.0,
+                &cell,
+                &GenerationContext::new(&control, false),
+            )
+        });
+        assert!(matches!(result, Err(TextureFailure::Cancelled)));
+        assert!(cell.inner.samples.load(Ordering::Relaxed) < 64 * 256 / 2);
+    }
+
+    /// `PendingTexture::cancel` reaches a running cancellable generator,
+    /// and the task reports `Cancelled` through its channel.
+    #[test]
+    fn cancel_stops_a_running_task() {
+        struct UntilCancelled;
+        impl CancellableGenerator for UntilCancelled {
+            fn generate_cancellable(
+                &self,
+                _width: u32,
+                _height: u32,
+                ctx: &GenerationContext,
+            ) -> Result<TextureMap, TextureFailure> {
+                let deadline = Instant::now() + Duration::from_secs(30);
+                while !ctx.is_cancelled() {
+                    assert!(Instant::now() < deadline, "cancellation never arrived");
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(TextureFailure::Cancelled)
+            }
+        }
+
+        let pending = PendingTexture::cancellable(UntilCancelled, 8, 8);
+        pending.cancel();
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let result = loop {
+            match pending.try_take(true) {
+                Ok(result) => break result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    assert!(Instant::now() < deadline, "cancelled task never reported");
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    panic!("worker lost the task")
+                }
+            }
+        };
+        assert!(matches!(result, Err(TextureFailure::Cancelled)));
+    }
+}
@@ -52,7 +52,7 @@ use bevy::prelude::{Handle, Res};
 use bevy::time::Time;
 
 use crate::cache::TextureCacheKey;
-use crate::material::{PatchMaterialTextures, TextureConfig};
+use crate::material::{PatchMaterialTextures, TextureConfig, TextureSlots};
 
 /// A function from a normalised or absolute time `t` to a value of type `T`.
 ///
@@ -185,15 +185,16 @@ where
 pub type TextureCurve = Arc<dyn Fn(f32) -> TextureConfig + Send + Sync>;
 
 /// Component that drives a procedural-texture refresh on a target
-/// [`StandardMaterial`] as time advances.
+/// material — a [`StandardMaterial`] unless `M` names another
+/// [`TextureSlots`] type — as time advances.
 ///
 /// Spawned alongside the material; consumed by
 /// [`tick_animated_procedural_materials`], which re-runs `texture_curve`
 /// each tick and dispatches a new generation when the result changes.
 #[derive(Component)]
-pub struct AnimatedProceduralMaterial {
+pub struct AnimatedProceduralMaterial<M: TextureSlots = StandardMaterial> {
     /// Material whose texture slots will be patched.
-    pub material: Handle<StandardMaterial>,
+    pub material: Handle<M>,
     /// Texture width (texels) for every regeneration.
     pub width: u32,
     /// Texture height (texels) for every regeneration.
@@ -203,6 +204,11 @@ pub struct AnimatedProceduralMaterial {
     /// Minimum delay between regeneration attempts.  Lower = smoother but
     /// more CPU; higher = jumpier but cheap.  Default `0.25 s`.
     pub min_regen_interval: f32,
+    /// Also regenerate the height map bound as the material's `depth_map`.
+    /// Set for materials built with a positive
+    /// [`parallax_depth_scale`](crate::material::MaterialSettings::parallax_depth_scale),
+    /// otherwise each regeneration unbinds their relief.  Default `false`.
+    pub height_map: bool,
     /// Internal: total elapsed time since this component was spawned.
     pub elapsed: f32,
     /// Internal: time of the last regeneration attempt (success or skip).
@@ -212,7 +218,7 @@ pub struct AnimatedProceduralMaterial {
     pub last_fingerprint: u64,
 }
 
-impl AnimatedProceduralMaterial {
+impl<M: TextureSlots> AnimatedProceduralMaterial<M> {
     /// Default `min_regen_interval` — quarter-second cadence balances
     /// visible motion against pool saturation at typical 256–1024 px sizes.
     pub const DEFAULT_REGEN_INTERVAL: f32 = 0.25;
@@ -222,7 +228,7 @@ impl AnimatedProceduralMaterial {
     /// the static material was created with so the first regeneration is
     /// a true delta.
     pub fn new(
-        material: Handle<StandardMaterial>,
+        material: Handle<M>,
         width: u32,
         height: u32,
         texture_curve: impl Fn(f32) -> TextureConfig + Send + Sync + 'static,
@@ -233,6 +239,7 @@ impl AnimatedProceduralMaterial {
             height,
             texture_curve: Arc::new(texture_curve),
             min_regen_interval: Self::DEFAULT_REGEN_INTERVAL,
+            height_map: false,
             elapsed: 0.0,
             last_regen_at: f32::NEG_INFINITY,
             last_fingerprint: 0,
@@ -245,18 +252,26 @@ impl AnimatedProceduralMaterial {
         self.min_regen_interval = interval.max(0.0);
         self
     }
+
+    /// Sets [`height_map`](Self::height_map) and returns `self` for
+    /// chaining.
+    pub fn with_height_map(mut self, height_map: bool) -> Self {
+        self.height_map = height_map;
+        self
+    }
 }
 
 /// Bevy system — advances every [`AnimatedProceduralMaterial`]'s clock,
 /// re-evaluates its curve, and dispatches a generation task whenever the
 /// fingerprint of the next config differs from the previous one.
 ///
-/// Registered automatically by
-/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin).
-pub fn tick_animated_procedural_materials(
+/// Registered automatically for [`StandardMaterial`] by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin), and for other
+/// types by [`ProceduralMaterialPlugin`](crate::ProceduralMaterialPlugin).
+pub fn tick_animated_procedural_materials<M: TextureSlots>(
     time: Res<Time>,
     mut commands: Commands,
-    mut anim_q: Query<(Entity, &mut AnimatedProceduralMaterial)>,
+    mut anim_q: Query<(Entity, &mut AnimatedProceduralMaterial<M>)>,
 ) {
     let dt = time.delta_secs();
 
@@ -284,7 +299,12 @@ pub fn tick_animated_procedural_materials(
             height: anim.height,
         };
 
-        if let Some(pending) = cfg.spawn(anim.width, anim.height) {
+        let pending = if anim.height_map {
+            cfg.spawn_with_height(anim.width, anim.height)
+        } else {
+            cfg.spawn(anim.width, anim.height)
+        };
+        if let Some(pending) = pending {
             commands.spawn((
                 pending,
                 PatchMaterialTextures {
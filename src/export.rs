@@ -0,0 +1,748 @@
+//! Writing generated textures to disk as standard image files.
+//!
+//! [`export_png`] writes every map of a [`TextureMap`] as an RGBA8 PNG so
+//! generated textures can be checked into an art repository or handed to
+//! tools that do not run Bevy.  Colour maps (albedo, emissive) carry an
+//! `sRGB` chunk; data maps (normal, ORM) carry `gAMA = 1.0` so viewers and
+//! importers treat them as linear instead of applying a display curve.
+//!
+//! ```rust,ignore
+//! let map = BrickGenerator::new(BrickConfig::default()).generate(512, 512)?;
+//! let written = map.export_png("art/bricks", "bricks", &PngExportOptions::default())?;
+//! // art/bricks/bricks_albedo.png, bricks_normal.png, bricks_orm.png
+//! ```
+//!
+//! Both exporters are also methods on [`TextureMap`] through the
+//! [`TextureMapExport`] extension trait, as above.
+//!
+//! [`export_ktx2`] writes the same maps as uncompressed KTX2 containers for
+//! shipping baked textures: the full mip chain from
+//! [`TextureMap::with_mips`] is stored as-is, albedo/emissive use
+//! `R8G8B8A8_SRGB` and normal/ORM `R8G8B8A8_UNORM` (the formats
+//! [`map_to_images`](crate::generator::map_to_images) uploads with), and the
+//! intended sampler address mode (repeat for surfaces, clamp for cards) is
+//! recorded under the [`KTX2_ADDRESS_MODE_KEY`] metadata key — read it back
+//! with [`ktx2_address_mode`].
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use bevy::image::ImageAddressMode;
+
+use crate::generator::TextureMap;
+
+/// How the pixel values of an exported image are encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportColorSpace {
+    /// sRGB-encoded colour — albedo and emissive maps.
+    Srgb,
+    /// Linear data — normal and ORM maps.
+    Linear,
+}
+
+/// Options for [`export_png`].
+#[derive(Clone, Debug, Default)]
+pub struct PngExportOptions {
+    /// Also write every mip level below the base as `<stem>_<map>_mip<N>.png`.
+    ///
+    /// Uses the chain already stored in the [`TextureMap`] when present and
+    /// computes it (with the same per-map filtering as upload) otherwise.
+    pub mip_levels: bool,
+}
+
+/// Errors raised while exporting a texture.
+#[derive(Debug)]
+pub enum ExportError {
+    /// Creating the output directory or a file failed.
+    Io(std::io::Error),
+    /// The PNG encoder rejected the image.
+    Png(png::EncodingError),
+    /// A pixel buffer is shorter than its declared dimensions require.
+    BufferTooShort {
+        /// Bytes required by the declared dimensions and mip count.
+        expected: usize,
+        /// Bytes actually present.
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "texture export I/O failed: {e}"),
+            Self::Png(e) => write!(f, "PNG encoding failed: {e}"),
+            Self::BufferTooShort { expected, actual } => write!(
+                f,
+                "pixel buffer holds {actual} bytes but {expected} are required"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(e: png::EncodingError) -> Self {
+        Self::Png(e)
+    }
+}
+
+/// One level of a base-plus-mips RGBA8 buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct MipLevel {
+    pub width: u32,
+    pub height: u32,
+    /// Byte offset of the level within the buffer.
+    pub offset: usize,
+    /// Byte length of the level (`width × height × 4`).
+    pub len: usize,
+}
+
+/// Layout of the `count` levels of a `width × height` RGBA8 chain, matching
+/// the halving rule of [`TextureMap::with_mips`] (each side floors at 1).
+pub(crate) fn mip_levels(width: u32, height: u32, count: u32) -> Vec<MipLevel> {
+    let (mut w, mut h, mut offset) = (width, height, 0usize);
+    (0..count)
+        .map(|_| {
+            let len = w as usize * h as usize * 4;
+            let level = MipLevel {
+                width: w,
+                height: h,
+                offset,
+                len,
+            };
+            offset += len;
+            w = (w / 2).max(1);
+            h = (h / 2).max(1);
+            level
+        })
+        .collect()
+}
+
+/// A copy of `map` with its mip chain appended, or `None` when `map`
+/// already carries one.  [`TextureMap`] is not `Clone`, hence the
+/// field-by-field copy.
+fn computed_chain(map: &TextureMap) -> Option<TextureMap> {
+    (map.mip_level_count <= 1).then(|| {
+        TextureMap {
+            albedo: map.albedo.clone(),
+            normal: map.normal.clone(),
+            roughness: map.roughness.clone(),
+            emissive: map.emissive.clone(),
+            width: map.width,
+            height: map.height,
+            mip_level_count: 1,
+        }
+        .with_mips()
+    })
+}
+
+/// The maps of `map` with their file-name suffix and colour space, in
+/// export order.
+fn named_maps(map: &TextureMap) -> Vec<(&'static str, &[u8], ExportColorSpace)> {
+    let mut maps = vec![
+        ("albedo", map.albedo.as_slice(), ExportColorSpace::Srgb),
+        ("normal", map.normal.as_slice(), ExportColorSpace::Linear),
+        ("orm", map.roughness.as_slice(), ExportColorSpace::Linear),
+    ];
+    if let Some(emissive) = &map.emissive {
+        maps.push(("emissive", emissive.as_slice(), ExportColorSpace::Srgb));
+    }
+    maps
+}
+
+/// Encode one RGBA8 image as PNG into `writer`.
+///
+/// `pixels` must hold at least `width × height × 4` bytes; any trailing
+/// bytes (e.g. appended mip levels) are ignored.
+pub fn encode_png<W: Write>(
+    writer: W,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    color_space: ExportColorSpace,
+) -> Result<(), ExportError> {
+    let expected = width as usize * height as usize * 4;
+    if pixels.len() < expected {
+        return Err(ExportError::BufferTooShort {
+            expected,
+            actual: pixels.len(),
+        });
+    }
+
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    match color_space {
+        ExportColorSpace::Srgb => encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual),
+        ExportColorSpace::Linear => encoder.set_source_gamma(png::ScaledFloat::new(1.0)),
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels[..expected])?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write every map of `map` into `dir` as `<stem>_albedo.png`,
+/// `<stem>_normal.png`, `<stem>_orm.png` and — when the generator produced
+/// one — `<stem>_emissive.png`, creating `dir` if needed.
+///
+/// Returns the paths written, base levels first in the order above, then
+/// (with [`PngExportOptions::mip_levels`]) each map's mip levels in turn.
+/// Existing files are overwritten.
+pub fn export_png(
+    map: &TextureMap,
+    dir: impl AsRef<Path>,
+    stem: &str,
+    options: &PngExportOptions,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+
+    let computed = if options.mip_levels {
+        computed_chain(map)
+    } else {
+        None
+    };
+    let source = computed.as_ref().unwrap_or(map);
+    let maps = named_maps(source);
+
+    let levels = if options.mip_levels {
+        mip_levels(source.width, source.height, source.mip_level_count)
+    } else {
+        mip_levels(source.width, source.height, 1)
+    };
+    let needed = levels.last().map_or(0, |l| l.offset + l.len);
+
+    let mut written = Vec::with_capacity(maps.len() * levels.len());
+    for (index, level) in levels.iter().enumerate() {
+        for &(name, pixels, color_space) in &maps {
+            if pixels.len() < needed {
+                return Err(ExportError::BufferTooShort {
+                    expected: needed,
+                    actual: pixels.len(),
+                });
+            }
+            let file_name = if index == 0 {
+                format!("{stem}_{name}.png")
+            } else {
+                format!("{stem}_{name}_mip{index}.png")
+            };
+            let path = dir.join(file_name);
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            encode_png(
+                file,
+                &pixels[level.offset..level.offset + level.len],
+                level.width,
+                level.height,
+                color_space,
+            )?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// KTX2 key/value entry recording the sampler address mode the texture was
+/// generated for: `repeat` (tileable surfaces) or `clamp_to_edge`
+/// (alpha-masked cards).
+pub const KTX2_ADDRESS_MODE_KEY: &str = "bevy_symbios_texture.addressMode";
+
+/// File identifier opening every KTX2 container.
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+/// `VK_FORMAT_R8G8B8A8_UNORM`.
+const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+/// `VK_FORMAT_R8G8B8A8_SRGB`.
+const VK_FORMAT_R8G8B8A8_SRGB: u32 = 43;
+/// Header (48 bytes) plus index (32 bytes); the level index follows.
+const KTX2_HEADER_LEN: usize = 80;
+/// Byte length of one level index entry.
+const KTX2_LEVEL_ENTRY_LEN: usize = 24;
+
+/// Options for [`export_ktx2`].
+#[derive(Clone, Debug, Default)]
+pub struct Ktx2ExportOptions {
+    /// Record clamp-to-edge sampling intent (alpha-masked cards, as
+    /// uploaded by [`map_to_images_card`](crate::generator::map_to_images_card))
+    /// instead of repeat (tileable surfaces).
+    pub is_card: bool,
+}
+
+fn address_mode_name(mode: ImageAddressMode) -> &'static str {
+    match mode {
+        ImageAddressMode::Repeat => "repeat",
+        ImageAddressMode::ClampToEdge => "clamp_to_edge",
+        ImageAddressMode::MirrorRepeat => "mirror_repeat",
+        ImageAddressMode::ClampToBorder => "clamp_to_border",
+    }
+}
+
+/// Basic data format descriptor for an RGBA8 texel (KTX2 §4, Khronos Data
+/// Format §5), including the leading `dfdTotalSize`.
+fn rgba8_dfd(color_space: ExportColorSpace) -> Vec<u8> {
+    const SAMPLES: usize = 4;
+    const BLOCK_LEN: u16 = 24 + 16 * SAMPLES as u16;
+    let mut dfd = Vec::with_capacity(4 + BLOCK_LEN as usize);
+    dfd.extend_from_slice(&(4 + u32::from(BLOCK_LEN)).to_le_bytes());
+    // vendorId = Khronos, descriptorType = basic.
+    dfd.extend_from_slice(&0u32.to_le_bytes());
+    dfd.extend_from_slice(&2u16.to_le_bytes()); // versionNumber
+    dfd.extend_from_slice(&BLOCK_LEN.to_le_bytes());
+    let transfer = match color_space {
+        ExportColorSpace::Srgb => 2,   // KHR_DF_TRANSFER_SRGB
+        ExportColorSpace::Linear => 1, // KHR_DF_TRANSFER_LINEAR
+    };
+    // colorModel RGBSDA, primaries BT.709, transfer, straight alpha.
+    dfd.extend_from_slice(&[1, 1, transfer, 0]);
+    dfd.extend_from_slice(&[0; 4]); // texelBlockDimension: 1×1×1×1
+    dfd.extend_from_slice(&[4, 0, 0, 0, 0, 0, 0, 0]); // bytesPlane0 = 4
+    for (index, channel) in [0u8, 1, 2, 15].into_iter().enumerate() {
+        // Alpha is never sRGB-encoded; flag it linear in sRGB formats.
+        let qualifiers = if channel == 15 && color_space == ExportColorSpace::Srgb {
+            0x10
+        } else {
+            0
+        };
+        dfd.extend_from_slice(&(index as u16 * 8).to_le_bytes()); // bitOffset
+        dfd.push(7); // bitLength - 1
+        dfd.push(channel | qualifiers);
+        dfd.extend_from_slice(&[0; 4]); // samplePosition
+        dfd.extend_from_slice(&0u32.to_le_bytes()); // sampleLower
+        dfd.extend_from_slice(&255u32.to_le_bytes()); // sampleUpper
+    }
+    dfd
+}
+
+/// Key/value data, sorted by key as the spec requires, each entry padded
+/// to four bytes.
+fn key_value_data(address_mode: ImageAddressMode) -> Vec<u8> {
+    let writer = concat!("bevy_symbios_texture ", env!("CARGO_PKG_VERSION"));
+    let mut entries = [
+        ("KTXorientation", "rd"),
+        ("KTXwriter", writer),
+        (KTX2_ADDRESS_MODE_KEY, address_mode_name(address_mode)),
+    ];
+    entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+    let mut kvd = Vec::new();
+    for (key, value) in entries {
+        // Key and value are both NUL-terminated UTF-8.
+        let len = key.len() + 1 + value.len() + 1;
+        kvd.extend_from_slice(&(len as u32).to_le_bytes());
+        kvd.extend_from_slice(key.as_bytes());
+        kvd.push(0);
+        kvd.extend_from_slice(value.as_bytes());
+        kvd.push(0);
+        kvd.resize(kvd.len().next_multiple_of(4), 0);
+    }
+    kvd
+}
+
+/// Encode one RGBA8 image and its mip chain as an uncompressed KTX2
+/// container into `writer`.
+///
+/// `pixels` holds `mip_level_count` levels in the base-plus-mips layout of
+/// [`TextureMap`]; the levels are written unmodified.  `color_space`
+/// selects `R8G8B8A8_SRGB` or `R8G8B8A8_UNORM`, and `address_mode` is
+/// recorded under [`KTX2_ADDRESS_MODE_KEY`].
+pub fn encode_ktx2<W: Write>(
+    mut writer: W,
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    mip_level_count: u32,
+    color_space: ExportColorSpace,
+    address_mode: ImageAddressMode,
+) -> Result<(), ExportError> {
+    let levels = mip_levels(width, height, mip_level_count.max(1));
+    let expected = levels.last().map_or(0, |l| l.offset + l.len);
+    if pixels.len() < expected {
+        return Err(ExportError::BufferTooShort {
+            expected,
+            actual: pixels.len(),
+        });
+    }
+
+    let dfd = rgba8_dfd(color_space);
+    let kvd = key_value_data(address_mode);
+    let dfd_offset = KTX2_HEADER_LEN + KTX2_LEVEL_ENTRY_LEN * levels.len();
+    let kvd_offset = dfd_offset + dfd.len();
+    // Level data must be 4-byte aligned; DFD and KVD lengths already are.
+    let data_offset = (kvd_offset + kvd.len()).next_multiple_of(4);
+
+    // The file stores the smallest level first; the index lists level 0
+    // first.  Every level length is a multiple of four, so no padding.
+    let mut level_offsets = vec![0usize; levels.len()];
+    let mut cursor = data_offset;
+    for (index, level) in levels.iter().enumerate().rev() {
+        level_offsets[index] = cursor;
+        cursor += level.len;
+    }
+
+    let vk_format = match color_space {
+        ExportColorSpace::Srgb => VK_FORMAT_R8G8B8A8_SRGB,
+        ExportColorSpace::Linear => VK_FORMAT_R8G8B8A8_UNORM,
+    };
+    let mut head = Vec::with_capacity(data_offset);
+    head.extend_from_slice(&KTX2_IDENTIFIER);
+    for field in [
+        vk_format,
+        1, // typeSize
+        width,
+        height,
+        0, // pixelDepth
+        0, // layerCount
+        1, // faceCount
+        levels.len() as u32,
+        0, // supercompressionScheme
+        dfd_offset as u32,
+        dfd.len() as u32,
+        kvd_offset as u32,
+        kvd.len() as u32,
+    ] {
+        head.extend_from_slice(&field.to_le_bytes());
+    }
+    head.extend_from_slice(&0u64.to_le_bytes()); // sgdByteOffset
+    head.extend_from_slice(&0u64.to_le_bytes()); // sgdByteLength
+    for (level, offset) in levels.iter().zip(&level_offsets) {
+        head.extend_from_slice(&(*offset as u64).to_le_bytes());
+        head.extend_from_slice(&(level.len as u64).to_le_bytes());
+        head.extend_from_slice(&(level.len as u64).to_le_bytes());
+    }
+    head.extend_from_slice(&dfd);
+    head.extend_from_slice(&kvd);
+    head.resize(data_offset, 0);
+
+    writer.write_all(&head)?;
+    for level in levels.iter().rev() {
+        writer.write_all(&pixels[level.offset..level.offset + level.len])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write every map of `map` into `dir` as `<stem>_albedo.ktx2`,
+/// `<stem>_normal.ktx2`, `<stem>_orm.ktx2` and — when the generator
+/// produced one — `<stem>_emissive.ktx2`, creating `dir` if needed.
+///
+/// Intended for maps from [`TextureMap::with_mips`], whose chain is stored
+/// verbatim so loading the file does no further work; a base-only map gets
+/// its chain computed first.  Returns the paths written.
+pub fn export_ktx2(
+    map: &TextureMap,
+    dir: impl AsRef<Path>,
+    stem: &str,
+    options: &Ktx2ExportOptions,
+) -> Result<Vec<PathBuf>, ExportError> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+
+    let computed = computed_chain(map);
+    let source = computed.as_ref().unwrap_or(map);
+    let address_mode = if options.is_card {
+        ImageAddressMode::ClampToEdge
+    } else {
+        ImageAddressMode::Repeat
+    };
+
+    let mut written = Vec::new();
+    for (name, pixels, color_space) in named_maps(source) {
+        let path = dir.join(format!("{stem}_{name}.ktx2"));
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        encode_ktx2(
+            file,
+            pixels,
+            source.width,
+            source.height,
+            source.mip_level_count,
+            color_space,
+            address_mode,
+        )?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// [`export_png`] and [`export_ktx2`] as methods, so the exporters are
+/// found where the map is held:
+///
+/// ```rust,ignore
+/// use bevy_symbios_texture::TextureMapExport;
+///
+/// let map = generator.generate(1024, 1024)?.with_mips();
+/// map.export_ktx2("assets/baked", "bricks", &Ktx2ExportOptions::default())?;
+/// ```
+pub trait TextureMapExport {
+    /// Write every map as PNG; see [`export_png`].
+    fn export_png(
+        &self,
+        dir: impl AsRef<Path>,
+        stem: &str,
+        options: &PngExportOptions,
+    ) -> Result<Vec<PathBuf>, ExportError>;
+
+    /// Write every map as KTX2; see [`export_ktx2`].
+    fn export_ktx2(
+        &self,
+        dir: impl AsRef<Path>,
+        stem: &str,
+        options: &Ktx2ExportOptions,
+    ) -> Result<Vec<PathBuf>, ExportError>;
+}
+
+impl TextureMapExport for TextureMap {
+    fn export_png(
+        &self,
+        dir: impl AsRef<Path>,
+        stem: &str,
+        options: &PngExportOptions,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        export_png(self, dir, stem, options)
+    }
+
+    fn export_ktx2(
+        &self,
+        dir: impl AsRef<Path>,
+        stem: &str,
+        options: &Ktx2ExportOptions,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        export_ktx2(self, dir, stem, options)
+    }
+}
+
+/// Read the sampler address mode recorded by [`encode_ktx2`] from a KTX2
+/// file's key/value data.
+///
+/// Returns `None` for files that are not KTX2, are truncated, or carry no
+/// (or an unknown) [`KTX2_ADDRESS_MODE_KEY`] entry.
+pub fn ktx2_address_mode(bytes: &[u8]) -> Option<ImageAddressMode> {
+    if bytes.get(..12)? != KTX2_IDENTIFIER {
+        return None;
+    }
+    let u32_at = |at: usize| -> Option<usize> {
+        Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?) as usize)
+    };
+    let kvd_offset = u32_at(56)?;
+    let kvd = bytes.get(kvd_offset..kvd_offset.checked_add(u32_at(60)?)?)?;
+
+    let mut cursor = 0;
+    while cursor + 4 <= kvd.len() {
+        let len = u32::from_le_bytes(kvd[cursor..cursor + 4].try_into().ok()?) as usize;
+        let entry = kvd.get(cursor + 4..cursor + 4 + len)?;
+        cursor = (cursor + 4 + len).next_multiple_of(4);
+
+        let split = entry.iter().position(|&b| b == 0)?;
+        if &entry[..split] != KTX2_ADDRESS_MODE_KEY.as_bytes() {
+            continue;
+        }
+        let value = entry[split + 1..]
+            .strip_suffix(&[0])
+            .unwrap_or(&entry[split + 1..]);
+        return [
+            ImageAddressMode::Repeat,
+            ImageAddressMode::ClampToEdge,
+            ImageAddressMode::MirrorRepeat,
+            ImageAddressMode::ClampToBorder,
+        ]
+        .into_iter()
+        .find(|&mode| address_mode_name(mode).as_bytes() == value);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::generator::TextureGenerator;
+    use crate::lava::{LavaConfig, LavaGenerator};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bst-export-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn decode(path: &Path) -> (png::Info<'static>, Vec<u8>) {
+        let file = std::io::BufReader::new(std::fs::File::open(path).expect("open png"));
+        let mut reader = png::Decoder::new(file).read_info().expect("png header");
+        let mut buf = vec![0; reader.output_buffer_size().expect("buffer size")];
+        let frame = reader.next_frame(&mut buf).expect("png frame");
+        buf.truncate(frame.buffer_size());
+        (reader.info().clone(), buf)
+    }
+
+    #[test]
+    fn mip_layout_matches_with_mips() {
+        let levels = mip_levels(8, 2, 4);
+        let dims: Vec<_> = levels.iter().map(|l| (l.width, l.height)).collect();
+        assert_eq!(dims, [(8, 2), (4, 1), (2, 1), (1, 1)]);
+        assert_eq!(levels[1].offset, 8 * 2 * 4);
+        assert_eq!(levels[3].offset + levels[3].len, (16 + 4 + 2 + 1) * 4);
+    }
+
+    #[test]
+    fn writes_tagged_pngs_round_trip() {
+        let map = LavaGenerator::new(LavaConfig::default())
+            .generate(8, 8)
+            .expect("generate");
+        let dir = temp_dir("base");
+        let written = export_png(&map, &dir, "lava", &PngExportOptions::default()).expect("export");
+        let names: Vec<_> = written
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "lava_albedo.png",
+                "lava_normal.png",
+                "lava_orm.png",
+                "lava_emissive.png"
+            ]
+        );
+
+        let (info, pixels) = decode(&written[0]);
+        assert_eq!((info.width, info.height), (8, 8));
+        assert!(info.srgb.is_some(), "albedo is tagged sRGB");
+        assert_eq!(pixels, map.albedo[..map.base_len()]);
+
+        let (info, pixels) = decode(&written[1]);
+        assert!(info.srgb.is_none(), "normal map must not be tagged sRGB");
+        assert_eq!(
+            info.gama_chunk.map(png::ScaledFloat::into_scaled),
+            Some(100_000),
+            "normal map is tagged linear"
+        );
+        assert_eq!(pixels, map.normal[..map.base_len()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// The extension-trait methods write the same files as the free
+    /// functions.
+    #[test]
+    fn texture_map_export_methods_match_free_functions() {
+        let map = LavaGenerator::new(LavaConfig::default())
+            .generate(8, 8)
+            .expect("generate");
+        let dir = temp_dir("trait");
+        let png = map
+            .export_png(&dir, "lava", &PngExportOptions::default())
+            .expect("png");
+        let ktx2 = map
+            .export_ktx2(&dir, "lava", &Ktx2ExportOptions::default())
+            .expect("ktx2");
+        assert_eq!(png.len(), 4);
+        assert_eq!(ktx2.len(), 4);
+        let (_, pixels) = decode(&png[0]);
+        assert_eq!(pixels, map.albedo[..map.base_len()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_every_mip_level_on_request() {
+        let map = LavaGenerator::new(LavaConfig::default())
+            .generate(8, 4)
+            .expect("generate");
+        let dir = temp_dir("mips");
+        let options = PngExportOptions { mip_levels: true };
+        let written = export_png(&map, &dir, "lava", &options).expect("export");
+        // 8×4 → 4×2 → 2×1 → 1×1: four levels of four maps.
+        assert_eq!(written.len(), 16);
+
+        let (info, pixels) = decode(&dir.join("lava_orm_mip3.png"));
+        assert_eq!((info.width, info.height), (1, 1));
+        let chained = map.with_mips();
+        let last = mip_levels(8, 4, chained.mip_level_count)[3];
+        assert_eq!(
+            pixels,
+            chained.roughness[last.offset..last.offset + last.len]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Bevy's own KTX2 reader accepts the container and recovers the exact
+    /// mip chain and format that `map_to_images` would upload.
+    #[test]
+    fn ktx2_round_trips_through_bevy_loader() {
+        use bevy::image::{CompressedImageFormats, ktx2_buffer_to_image};
+        use bevy::render::render_resource::TextureFormat;
+
+        let map = LavaGenerator::new(LavaConfig::default())
+            .generate(8, 4)
+            .expect("generate")
+            .with_mips();
+        let dir = temp_dir("ktx2");
+        let written =
+            export_ktx2(&map, &dir, "lava", &Ktx2ExportOptions::default()).expect("export");
+        assert_eq!(written.len(), 4, "albedo, normal, orm, emissive");
+
+        let albedo = std::fs::read(dir.join("lava_albedo.ktx2")).expect("read albedo");
+        let image = ktx2_buffer_to_image(&albedo, CompressedImageFormats::NONE, true)
+            .expect("bevy parses albedo");
+        assert_eq!(
+            image.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(
+            image.texture_descriptor.mip_level_count,
+            map.mip_level_count
+        );
+        assert_eq!(image.data.as_deref(), Some(map.albedo.as_slice()));
+        assert_eq!(ktx2_address_mode(&albedo), Some(ImageAddressMode::Repeat));
+
+        let normal = std::fs::read(dir.join("lava_normal.ktx2")).expect("read normal");
+        let image = ktx2_buffer_to_image(&normal, CompressedImageFormats::NONE, false)
+            .expect("bevy parses normal");
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(image.data.as_deref(), Some(map.normal.as_slice()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ktx2_records_card_sampler_intent() {
+        let map = LavaGenerator::new(LavaConfig::default())
+            .generate(4, 4)
+            .expect("generate");
+        let mut bytes = Vec::new();
+        encode_ktx2(
+            &mut bytes,
+            &map.albedo,
+            4,
+            4,
+            1,
+            ExportColorSpace::Srgb,
+            ImageAddressMode::ClampToEdge,
+        )
+        .expect("encode");
+        assert_eq!(
+            ktx2_address_mode(&bytes),
+            Some(ImageAddressMode::ClampToEdge)
+        );
+        assert_eq!(ktx2_address_mode(&bytes[..40]), None, "truncated file");
+        assert!(matches!(
+            encode_ktx2(
+                Vec::new(),
+                &map.albedo,
+                4,
+                4,
+                3,
+                ExportColorSpace::Srgb,
+                ImageAddressMode::Repeat,
+            ),
+            Err(ExportError::BufferTooShort { .. })
+        ));
+    }
+}
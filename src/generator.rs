@@ -31,6 +31,9 @@ pub struct GeneratedHandles {
     pub roughness: Handle<Image>,
     /// Handle to the emissive (glow) image, when the generator produced one.
     pub emissive: Option<Handle<Image>>,
+    /// Handle to the `R16Float` height image, when one was requested (see
+    /// [`HeightMap`](crate::height::HeightMap)).
+    pub height: Option<Handle<Image>>,
 }
 
 /// Controls how the upload-time mipmap fallback averages pixels for different
@@ -54,9 +57,11 @@ enum MipmapMode {
 /// buffer is released once the texture reaches the GPU. That is correct for
 /// textures bound straight to a material and never sampled back on the CPU, and
 /// a meaningful saving on wasm where linear memory never returns to the OS.
-/// Callers that read the pixels back after upload (e.g. concatenating layers
-/// into a texture array) must use [`map_to_images_with_usages`] with
-/// [`RenderAssetUsages::MAIN_WORLD`] to keep `Image::data` resident.
+/// Callers that read the pixels back after upload must use
+/// [`map_to_images_with_usages`] with [`RenderAssetUsages::MAIN_WORLD`] to
+/// keep `Image::data` resident.  For texture arrays, prefer
+/// [`PendingTextureArray`](crate::array::PendingTextureArray), which
+/// concatenates the layers before upload.
 pub fn map_to_images(map: TextureMap, images: &mut Assets<Image>) -> GeneratedHandles {
     map_to_images_with_usages(map, RenderAssetUsages::RENDER_WORLD, images)
 }
@@ -70,50 +75,7 @@ pub fn map_to_images_with_usages(
     usages: RenderAssetUsages,
     images: &mut Assets<Image>,
 ) -> GeneratedHandles {
-    GeneratedHandles {
-        albedo: images.add(make_image(
-            map.albedo,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8UnormSrgb,
-            ImageAddressMode::Repeat,
-            MipmapMode::Srgb,
-            usages,
-        )),
-        normal: images.add(make_image(
-            map.normal,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8Unorm,
-            ImageAddressMode::Repeat,
-            MipmapMode::Normal,
-            usages,
-        )),
-        roughness: images.add(make_image(
-            map.roughness,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8Unorm,
-            ImageAddressMode::Repeat,
-            MipmapMode::Linear,
-            usages,
-        )),
-        emissive: map.emissive.map(|data| {
-            images.add(make_image(
-                data,
-                map.width,
-                map.height,
-                map.mip_level_count,
-                TextureFormat::Rgba8UnormSrgb,
-                ImageAddressMode::Repeat,
-                MipmapMode::Srgb,
-                usages,
-            ))
-        }),
-    }
+    ImageSet::from_map(map, ImageAddressMode::Repeat, usages).add_to(images)
 }
 
 /// Upload a [`TextureMap`] into [`Assets<Image>`] with clamp-to-edge samplers.
@@ -138,49 +100,84 @@ pub fn map_to_images_card_with_usages(
     usages: RenderAssetUsages,
     images: &mut Assets<Image>,
 ) -> GeneratedHandles {
-    GeneratedHandles {
-        albedo: images.add(make_image(
-            map.albedo,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8UnormSrgb,
-            ImageAddressMode::ClampToEdge,
-            MipmapMode::Srgb,
-            usages,
-        )),
-        normal: images.add(make_image(
-            map.normal,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8Unorm,
-            ImageAddressMode::ClampToEdge,
-            MipmapMode::Normal,
-            usages,
-        )),
-        roughness: images.add(make_image(
-            map.roughness,
-            map.width,
-            map.height,
-            map.mip_level_count,
-            TextureFormat::Rgba8Unorm,
-            ImageAddressMode::ClampToEdge,
-            MipmapMode::Linear,
-            usages,
-        )),
-        emissive: map.emissive.map(|data| {
-            images.add(make_image(
-                data,
+    ImageSet::from_map(map, ImageAddressMode::ClampToEdge, usages).add_to(images)
+}
+
+/// The un-registered [`Image`]s built from one [`TextureMap`].
+///
+/// The `map_to_images*` helpers add these straight into [`Assets<Image>`];
+/// the `.symtex` asset loader instead registers them as labeled sub-assets
+/// through its `LoadContext`, which has no `Assets<Image>` to write into.
+pub(crate) struct ImageSet {
+    pub(crate) albedo: Image,
+    pub(crate) normal: Image,
+    pub(crate) roughness: Image,
+    pub(crate) emissive: Option<Image>,
+}
+
+impl ImageSet {
+    /// Build the albedo (sRGB), normal and ORM (linear) images, plus the
+    /// emissive (sRGB) image when present, all sampled with `address_mode`.
+    pub(crate) fn from_map(
+        map: TextureMap,
+        address_mode: ImageAddressMode,
+        usages: RenderAssetUsages,
+    ) -> Self {
+        Self {
+            albedo: make_image(
+                map.albedo,
                 map.width,
                 map.height,
                 map.mip_level_count,
                 TextureFormat::Rgba8UnormSrgb,
-                ImageAddressMode::ClampToEdge,
+                address_mode,
                 MipmapMode::Srgb,
                 usages,
-            ))
-        }),
+            ),
+            normal: make_image(
+                map.normal,
+                map.width,
+                map.height,
+                map.mip_level_count,
+                TextureFormat::Rgba8Unorm,
+                address_mode,
+                MipmapMode::Normal,
+                usages,
+            ),
+            roughness: make_image(
+                map.roughness,
+                map.width,
+                map.height,
+                map.mip_level_count,
+                TextureFormat::Rgba8Unorm,
+                address_mode,
+                MipmapMode::Linear,
+                usages,
+            ),
+            emissive: map.emissive.map(|data| {
+                make_image(
+                    data,
+                    map.width,
+                    map.height,
+                    map.mip_level_count,
+                    TextureFormat::Rgba8UnormSrgb,
+                    address_mode,
+                    MipmapMode::Srgb,
+                    usages,
+                )
+            }),
+        }
+    }
+
+    /// Register every image in `images` and return the handles.
+    fn add_to(self, images: &mut Assets<Image>) -> GeneratedHandles {
+        GeneratedHandles {
+            albedo: images.add(self.albedo),
+            normal: images.add(self.normal),
+            roughness: images.add(self.roughness),
+            emissive: self.emissive.map(|image| images.add(image)),
+            height: None,
+        }
     }
 }
 
@@ -216,7 +213,14 @@ fn make_image(
     );
     image.texture_descriptor.mip_level_count = mip_level_count;
     image.data = Some(mip_data);
-    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+    image.sampler = trilinear_sampler(address_mode);
+    image
+}
+
+/// Trilinear, 16× anisotropic sampler wrapping with `address_mode` — shared
+/// by every generated image, texture arrays included.
+pub(crate) fn trilinear_sampler(address_mode: ImageAddressMode) -> ImageSampler {
+    ImageSampler::Descriptor(ImageSamplerDescriptor {
         address_mode_u: address_mode,
         address_mode_v: address_mode,
         // wgpu requires all filter modes to be Linear when anisotropy_clamp > 1.
@@ -225,8 +229,7 @@ fn make_image(
         mipmap_filter: bevy::image::ImageFilterMode::Linear,
         anisotropy_clamp: 16,
         ..Default::default()
-    });
-    image
+    })
 }
 
 // --- upload-time mipmap fallback --------------------------------------------
@@ -254,7 +257,7 @@ fn srgb_to_linear(v: u8) -> f32 {
 }
 
 #[inline]
-fn linear_to_srgb(linear: f32) -> u8 {
+pub(crate) fn linear_to_srgb(linear: f32) -> u8 {
     const N: usize = 4096;
     static LUT: OnceLock<[u8; N]> = OnceLock::new();
     let lut = LUT.get_or_init(|| {
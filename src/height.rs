@@ -0,0 +1,652 @@
+//! Height (displacement) maps, exact or reconstructed from generated
+//! normal maps.
+//!
+//! Every generator builds a greyscale height field and converts it to a
+//! tangent-space normal map with
+//! [`height_to_normal`](crate::normal::height_to_normal), but the core
+//! [`TextureMap`] keeps only the normals.  Generators written against this
+//! crate keep the field itself: a
+//! [`CancellableGenerator`](crate::cancellable::CancellableGenerator)
+//! sampling through
+//! [`generate_surface_cancellable`](crate::cancellable::generate_surface_cancellable)
+//! hands it over exactly, via [`HeightMap::from_heights`].
+//!
+//! For the built-in generators, [`HeightMap`] can only reconstruct an
+//! approximation by integrating the normal map's slopes: it
+//! solves the Poisson equation `∇²h = ∇·g` (where `g` is the per-texel
+//! gradient encoded by the normals) with a coarse-to-fine Jacobi solver,
+//! toroidally for tileable surfaces and with reflecting edges for cards.
+//!
+//! This is a reconstruction, not the generator's field:
+//!
+//! - the normals are 8-bit, so gentle slopes are quantised and fine relief
+//!   below that precision is lost;
+//! - slopes steeper than the solver's normal-Z clamp are flattened, so deep
+//!   steps (mortar joints, cobble edges) come out shallower than generated;
+//! - every map is normalised to span the full `[0, 1]` range, so the same
+//!   `parallax_depth_scale` gives different physical depths on different
+//!   materials — a nearly flat stucco and a deep cobblestone both reach
+//!   `1.0`.
+//!
+//! It is good enough for parallax mapping, displacement and height-based
+//! blending within one material; tune depth per material rather than
+//! expecting it to match across materials.  It is a stopgap, not the
+//! generator's height channel: that needs `symbios-texture` to return the
+//! field alongside the other maps, at which point the solver becomes a
+//! fallback.
+//!
+//! The async paths compute it on the worker when asked to (see
+//! [`TextureConfig::spawn_with_height`](crate::TextureConfig::spawn_with_height)
+//! and [`PendingTexture::cancellable_with_height`](crate::async_gen::PendingTexture::cancellable_with_height));
+//! the handle then appears as [`GeneratedHandles::height`].  Synchronous
+//! callers build one with [`HeightMap::from_texture_map`] and upload it with
+//! [`HeightMap::into_image`].
+//!
+//! [`GeneratedHandles::height`]: crate::generator::GeneratedHandles::height
+
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{Image, ImageAddressMode},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use rayon::prelude::*;
+
+use crate::generator::{TextureMap, trilinear_sampler};
+use crate::normal::BoundaryMode;
+
+/// Jacobi sweeps on the coarsest pyramid level, where the solve starts from
+/// zero and the grid is at most a few texels across.
+const COARSE_SWEEPS: usize = 256;
+/// Jacobi sweeps on every finer level, starting from the upsampled coarser
+/// solution — enough to remove the interpolation error, which is
+/// high-frequency at that level.
+const REFINE_SWEEPS: usize = 24;
+/// Share of the compact 5-point Laplacian in the solver's stencil (see
+/// [`jacobi`]).
+const COMPACT_WEIGHT: f32 = 0.25;
+/// Damping of the Jacobi update; undamped Jacobi never removes the
+/// checkerboard mode on toroidal grids.
+const JACOBI_WEIGHT: f32 = 0.8;
+/// Pyramid levels stop halving once either side is at most this long.
+const COARSEST_SIDE: usize = 8;
+/// Lower bound on the normal's Z before dividing by it, so near-vertical
+/// texels give a steep but finite slope.
+const MIN_NORMAL_Z: f32 = 0.05;
+
+/// A 16-bit greyscale height map, row-major, one level.
+///
+/// `0` is the lowest point of the texture and `u16::MAX` the highest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeightMap {
+    /// Height texels, `width × height` of them.
+    pub data: Vec<u16>,
+    /// Width in texels.
+    pub width: u32,
+    /// Height in texels.
+    pub height: u32,
+}
+
+impl HeightMap {
+    /// Reconstruct the height field behind the base level of `map`'s
+    /// normal map (see the [module docs](self) for its limits).
+    ///
+    /// Use [`BoundaryMode::Wrap`] for tileable surfaces and
+    /// [`BoundaryMode::Clamp`] for cards, matching how the normals were
+    /// derived.
+    pub fn from_texture_map(map: &TextureMap, boundary: BoundaryMode) -> Self {
+        Self::from_texture_map_with(map, boundary, &|_| true).expect("never stopped")
+    }
+
+    /// [`from_texture_map`](HeightMap::from_texture_map) reporting to
+    /// `on_sweep` before every solver sweep with the fraction of sweeps
+    /// done.  Gives up, returning `None`, as soon as `on_sweep` returns
+    /// `false` — so a cancelled task frees its worker within milliseconds.
+    pub(crate) fn from_texture_map_with(
+        map: &TextureMap,
+        boundary: BoundaryMode,
+        on_sweep: &dyn Fn(f32) -> bool,
+    ) -> Option<Self> {
+        let base = map.width as usize * map.height as usize * 4;
+        Self::recover(
+            &map.normal[..base],
+            map.width,
+            map.height,
+            boundary,
+            on_sweep,
+        )
+    }
+
+    /// Quantise a generator's own `width × height` height field, row-major,
+    /// with its range remapped to `[0, 1]` (a flat field maps to
+    /// mid-grey).  Unlike the reconstructions this keeps the field's full
+    /// precision and every step, however steep.
+    ///
+    /// # Panics
+    /// If `heights` does not hold exactly `width × height` values.
+    pub fn from_heights(heights: &[f64], width: u32, height: u32) -> Self {
+        Self::from_heights_in_range(heights, width, height, None)
+    }
+
+    /// [`from_heights`](HeightMap::from_heights) mapping the fixed range
+    /// `lo..=hi` to `[0, 1]` instead of the field's own, clamping outliers
+    /// — so materials sharing a height scale also share a
+    /// `parallax_depth_scale`.  `None` uses the field's range.
+    ///
+    /// # Panics
+    /// If `heights` does not hold exactly `width × height` values.
+    pub fn from_heights_in_range(
+        heights: &[f64],
+        width: u32,
+        height: u32,
+        range: Option<(f64, f64)>,
+    ) -> Self {
+        assert_eq!(
+            heights.len(),
+            width as usize * height as usize,
+            "height field is not {width}x{height}"
+        );
+        let field: Vec<f32> = heights.iter().map(|&v| v as f32).collect();
+        match range {
+            Some((lo, hi)) => Self::normalised_in(&field, width, height, lo as f32, hi as f32),
+            None => Self::normalised(&field, width, height),
+        }
+    }
+
+    /// Recover the height field behind an RGBA8 tangent-space normal map
+    /// (the encoding of [`TextureMap::normal`]).
+    ///
+    /// # Panics
+    /// If `normal` holds fewer than `width × height × 4` bytes.
+    pub fn from_normal_map(normal: &[u8], width: u32, height: u32, boundary: BoundaryMode) -> Self {
+        Self::recover(normal, width, height, boundary, &|_| true).expect("never stopped")
+    }
+
+    fn recover(
+        normal: &[u8],
+        width: u32,
+        height: u32,
+        boundary: BoundaryMode,
+        on_sweep: &dyn Fn(f32) -> bool,
+    ) -> Option<Self> {
+        let (w, h) = (width as usize, height as usize);
+        assert!(normal.len() >= w * h * 4, "normal map shorter than {w}x{h}");
+        if w == 0 || h == 0 {
+            return Some(Self {
+                data: Vec::new(),
+                width,
+                height,
+            });
+        }
+
+        // Slopes per texel.  The core encodes normalize(-dh/du, -dh/dv, 1)
+        // in UV units with v running down the image, so the UV gradient is
+        // -n.xy / n.z; dividing by the side lengths turns it into a per-texel
+        // slope (up to a common scale, which normalisation removes).
+        let decode = |c: u8| c as f32 / 127.5 - 1.0;
+        let (gx, gy): (Vec<f32>, Vec<f32>) = normal
+            .par_chunks_exact(4)
+            .take(w * h)
+            .map(|n| {
+                let z = decode(n[2]).max(MIN_NORMAL_Z);
+                (
+                    -decode(n[0]) / (z * w as f32),
+                    -decode(n[1]) / (z * h as f32),
+                )
+            })
+            .unzip();
+
+        let grid = Grid { w, h, boundary };
+        let mut divergence: Vec<f32> = (0..w * h)
+            .into_par_iter()
+            .map(|i| {
+                let (x, y) = (i % w, i / w);
+                let [xm, xp, ym, yp] = grid.neighbours(x, y, 1);
+                0.5 * (gx[y * w + xp] - gx[y * w + xm]) + 0.5 * (gy[yp * w + x] - gy[ym * w + x])
+            })
+            .collect();
+        // Both boundary conditions only admit a solution for zero-mean
+        // sources; remove the quantisation drift.
+        let mean = divergence.iter().sum::<f32>() / divergence.len() as f32;
+        divergence.iter_mut().for_each(|d| *d -= mean);
+
+        let field = solve_poisson(grid, divergence, on_sweep)?;
+        Some(Self::normalised(&field, width, height))
+    }
+
+    /// Height at texel `(x, y)` in `[0, 1]`.
+    ///
+    /// # Panics
+    /// If `(x, y)` is outside the map.
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        assert!(x < self.width && y < self.height, "texel out of bounds");
+        self.data[(y * self.width + x) as usize] as f32 / u16::MAX as f32
+    }
+
+    /// Bytes of the image [`into_image`](HeightMap::into_image) builds:
+    /// two per texel over the full mip chain down to 1×1.
+    pub(crate) fn upload_len(&self) -> usize {
+        let (mut w, mut h) = (self.width as usize, self.height as usize);
+        let mut texels = w * h;
+        while w > 1 || h > 1 {
+            (w, h) = ((w / 2).max(1), (h / 2).max(1));
+            texels += w * h;
+        }
+        texels * 2
+    }
+
+    /// Upload as an `R16Float` image with a full mip chain, sampled with
+    /// `address_mode` like the other generated maps.
+    ///
+    /// `R16Float` rather than `R16Unorm` because every backend can filter
+    /// it without optional device features.  Pass
+    /// [`RenderAssetUsages::MAIN_WORLD`] to keep the texels CPU-resident
+    /// (e.g. for mesh displacement).
+    pub fn into_image(self, address_mode: ImageAddressMode, usages: RenderAssetUsages) -> Image {
+        let mut level: Vec<f32> = self
+            .data
+            .iter()
+            .map(|&v| v as f32 / u16::MAX as f32)
+            .collect();
+        let (mut w, mut h) = (self.width as usize, self.height as usize);
+        let mut bytes = Vec::with_capacity(self.upload_len());
+        let mut mip_level_count = 1;
+        loop {
+            bytes.extend(level.iter().flat_map(|&v| f16_bits(v).to_le_bytes()));
+            if w <= 1 && h <= 1 {
+                break;
+            }
+            let (nw, nh) = ((w / 2).max(1), (h / 2).max(1));
+            level = (0..nw * nh)
+                .map(|i| {
+                    let (x, y) = ((i % nw) * 2, (i / nw) * 2);
+                    let (mut sum, mut count) = (0.0, 0.0);
+                    for sy in y..(y + 2).min(h) {
+                        for sx in x..(x + 2).min(w) {
+                            sum += level[sy * w + sx];
+                            count += 1.0;
+                        }
+                    }
+                    sum / count
+                })
+                .collect();
+            (w, h) = (nw, nh);
+            mip_level_count += 1;
+        }
+
+        let mut image = Image::new_uninit(
+            Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            TextureFormat::R16Float,
+            usages,
+        );
+        image.texture_descriptor.mip_level_count = mip_level_count;
+        image.data = Some(bytes);
+        image.sampler = trilinear_sampler(address_mode);
+        image
+    }
+
+    /// Quantise `field` after remapping its range to `[0, 1]`; a flat field
+    /// maps to mid-grey.
+    fn normalised(field: &[f32], width: u32, height: u32) -> Self {
+        let (lo, hi) = field
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        Self::normalised_in(field, width, height, lo, hi)
+    }
+
+    /// Quantise `field` after remapping `lo..=hi` to `[0, 1]`, clamping
+    /// values outside it; an empty range maps to mid-grey.
+    fn normalised_in(field: &[f32], width: u32, height: u32, lo: f32, hi: f32) -> Self {
+        let range = hi - lo;
+        let data = field
+            .iter()
+            .map(|&v| {
+                let t = if range > 1e-6 {
+                    ((v - lo) / range).clamp(0.0, 1.0)
+                } else {
+                    0.5
+                };
+                (t * u16::MAX as f32).round() as u16
+            })
+            .collect();
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+/// Dimensions and edge behaviour of one pyramid level.
+#[derive(Clone, Copy)]
+struct Grid {
+    w: usize,
+    h: usize,
+    boundary: BoundaryMode,
+}
+
+impl Grid {
+    /// `[x - d, x + d, y - d, y + d]`, wrapped or clamped.
+    fn neighbours(&self, x: usize, y: usize, d: usize) -> [usize; 4] {
+        let (w, h) = (self.w, self.h);
+        match self.boundary {
+            BoundaryMode::Wrap => [
+                (x + w - d % w) % w,
+                (x + d) % w,
+                (y + h - d % h) % h,
+                (y + d) % h,
+            ],
+            BoundaryMode::Clamp => [
+                x.saturating_sub(d),
+                (x + d).min(w - 1),
+                y.saturating_sub(d),
+                (y + d).min(h - 1),
+            ],
+        }
+    }
+
+    /// The next coarser level: each side halved, rounding up.
+    fn coarser(&self) -> Self {
+        Self {
+            w: self.w.div_ceil(2),
+            h: self.h.div_ceil(2),
+            ..*self
+        }
+    }
+}
+
+/// Solve `∇²h = source` on `grid`, coarse to fine: restrict the source down
+/// a pyramid, solve the coarsest level outright, then upsample each
+/// solution as the starting point for the next finer level.  `None` once
+/// `on_sweep` asks to stop (see [`Sweeps`]).
+fn solve_poisson(grid: Grid, source: Vec<f32>, on_sweep: &dyn Fn(f32) -> bool) -> Option<Vec<f32>> {
+    let mut levels = vec![(grid, source)];
+    loop {
+        let (fine, fine_source) = levels.last().expect("non-empty pyramid");
+        if fine.w.min(fine.h) <= COARSEST_SIDE {
+            break;
+        }
+        let coarse = fine.coarser();
+        let source = restrict(*fine, fine_source, coarse);
+        levels.push((coarse, source));
+    }
+
+    let mut sweeps = Sweeps {
+        done: 0,
+        total: COARSE_SWEEPS + REFINE_SWEEPS * (levels.len() - 1),
+        on_sweep,
+    };
+    let (coarsest, source) = levels.pop().expect("non-empty pyramid");
+    let mut field = jacobi(
+        coarsest,
+        &source,
+        vec![0.0; source.len()],
+        COARSE_SWEEPS,
+        &mut sweeps,
+    )?;
+    let mut current = coarsest;
+    while let Some((fine, source)) = levels.pop() {
+        let guess = prolong(current, &field, fine);
+        field = jacobi(fine, &source, guess, REFINE_SWEEPS, &mut sweeps)?;
+        current = fine;
+    }
+    Some(field)
+}
+
+/// Sweep counter threaded through a [`solve_poisson`] run.
+struct Sweeps<'a> {
+    done: usize,
+    total: usize,
+    /// Told the fraction of sweeps done before each sweep; `false` stops
+    /// the solve.
+    on_sweep: &'a dyn Fn(f32) -> bool,
+}
+
+impl Sweeps<'_> {
+    /// Report the coming sweep; `false` when the solve should stop.
+    fn next(&mut self) -> bool {
+        let go_on = (self.on_sweep)(self.done as f32 / self.total as f32);
+        self.done += 1;
+        go_on
+    }
+}
+
+/// Sum each 2×2 block of `source` into one `coarse` texel (scaled up when
+/// an odd edge leaves the block partial).  Summing rather than averaging
+/// keeps the equation consistent: at twice the texel spacing the Laplacian
+/// of the same field is four times larger.
+fn restrict(fine: Grid, source: &[f32], coarse: Grid) -> Vec<f32> {
+    (0..coarse.w * coarse.h)
+        .into_par_iter()
+        .map(|i| {
+            let (x, y) = ((i % coarse.w) * 2, (i / coarse.w) * 2);
+            let (mut sum, mut count) = (0.0, 0.0);
+            for sy in y..(y + 2).min(fine.h) {
+                for sx in x..(x + 2).min(fine.w) {
+                    sum += source[sy * fine.w + sx];
+                    count += 1.0;
+                }
+            }
+            sum * 4.0 / count
+        })
+        .collect()
+}
+
+/// Bilinearly upsample a `coarse` solution onto `fine` texel centres.
+fn prolong(coarse: Grid, field: &[f32], fine: Grid) -> Vec<f32> {
+    let sample = |x: isize, y: isize| {
+        let (x, y) = match coarse.boundary {
+            BoundaryMode::Wrap => (
+                x.rem_euclid(coarse.w as isize) as usize,
+                y.rem_euclid(coarse.h as isize) as usize,
+            ),
+            BoundaryMode::Clamp => (
+                x.clamp(0, coarse.w as isize - 1) as usize,
+                y.clamp(0, coarse.h as isize - 1) as usize,
+            ),
+        };
+        field[y * coarse.w + x]
+    };
+    (0..fine.w * fine.h)
+        .into_par_iter()
+        .map(|i| {
+            let fx = ((i % fine.w) as f32 + 0.5) * 0.5 - 0.5;
+            let fy = ((i / fine.w) as f32 + 0.5) * 0.5 - 0.5;
+            let (x0, y0) = (fx.floor(), fy.floor());
+            let (tx, ty) = (fx - x0, fy - y0);
+            let (x0, y0) = (x0 as isize, y0 as isize);
+            let top = sample(x0, y0) * (1.0 - tx) + sample(x0 + 1, y0) * tx;
+            let bottom = sample(x0, y0 + 1) * (1.0 - tx) + sample(x0 + 1, y0 + 1) * tx;
+            top * (1.0 - ty) + bottom * ty
+        })
+        .collect()
+}
+
+/// Damped Jacobi sweeps of the blended Laplacian stencil.  Clamped edges
+/// drop the missing neighbours (a zero-slope reflecting boundary).
+///
+/// The source is the central-difference divergence of central-difference
+/// slopes, whose consistent Laplacian is the wide stencil (neighbours two
+/// texels away, weight ¼).  Solving with the compact 5-point stencil
+/// instead flattens fine detail by `cos²(k/2)` at frequency `k`; the wide
+/// stencil alone leaves the four interleaved sub-lattices uncoupled.
+/// Blending in [`COMPACT_WEIGHT`] of the compact stencil couples them
+/// while only touching the highest frequencies.
+///
+/// Returns `None` as soon as `counter` says to stop between sweeps.
+fn jacobi(
+    grid: Grid,
+    source: &[f32],
+    mut field: Vec<f32>,
+    sweeps: usize,
+    counter: &mut Sweeps,
+) -> Option<Vec<f32>> {
+    let wide_weight = (1.0 - COMPACT_WEIGHT) / 4.0;
+    let mut next = vec![0.0; field.len()];
+    for _ in 0..sweeps {
+        if !counter.next() {
+            return None;
+        }
+        next.par_chunks_mut(grid.w)
+            .enumerate()
+            .for_each(|(y, row)| {
+                for (x, out) in row.iter_mut().enumerate() {
+                    let mut sum = 0.0;
+                    let mut count = 0.0;
+                    for (d, weight) in [(1, COMPACT_WEIGHT), (2, wide_weight)] {
+                        let [xm, xp, ym, yp] = grid.neighbours(x, y, d);
+                        for (nx, ny) in [(xm, y), (xp, y), (x, ym), (x, yp)] {
+                            if (nx, ny) != (x, y) {
+                                sum += weight * field[ny * grid.w + nx];
+                                count += weight;
+                            }
+                        }
+                    }
+                    let current = field[y * grid.w + x];
+                    if count > 0.0 {
+                        let target = (sum - source[y * grid.w + x]) / count;
+                        *out = current + JACOBI_WEIGHT * (target - current);
+                    } else {
+                        *out = current;
+                    }
+                }
+            });
+        std::mem::swap(&mut field, &mut next);
+    }
+    Some(field)
+}
+
+/// IEEE binary16 bits of `v`, which must lie in `[0, 1]`.
+fn f16_bits(v: f32) -> u16 {
+    let v = v.clamp(0.0, 1.0);
+    // Below the smallest normal half: subnormal steps of 2^-24.
+    if v < 6.103_515_6e-5 {
+        return (v * 16_777_216.0).round() as u16;
+    }
+    let bits = v.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+    let half = ((exponent as u32) << 10) | (mantissa >> 13);
+    // Round to nearest; a carry out of the mantissa bumps the exponent,
+    // which is the correctly rounded result.
+    (half + ((mantissa >> 12) & 1)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::normal::height_to_normal;
+
+    /// A smooth tileable bump field with several frequencies.
+    fn periodic_field(w: usize, h: usize) -> Vec<f64> {
+        use std::f64::consts::TAU;
+        (0..w * h)
+            .map(|i| {
+                let u = (i % w) as f64 / w as f64;
+                let v = (i / w) as f64 / h as f64;
+                (TAU * u).sin() * 0.5
+                    + (TAU * 3.0 * v).cos() * 0.3
+                    + (TAU * (2.0 * u + v)).sin() * 0.2
+            })
+            .collect()
+    }
+
+    fn normalise(field: &[f64]) -> Vec<f64> {
+        let lo = field.iter().cloned().fold(f64::INFINITY, f64::min);
+        let hi = field.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        field.iter().map(|v| (v - lo) / (hi - lo)).collect()
+    }
+
+    #[test]
+    fn recovers_tileable_height_field() {
+        let (w, h) = (64, 48);
+        let truth = normalise(&periodic_field(w, h));
+        let normal = height_to_normal(&truth, w as u32, h as u32, 32.0, BoundaryMode::Wrap);
+        let recovered = HeightMap::from_normal_map(&normal, w as u32, h as u32, BoundaryMode::Wrap);
+
+        let max_error = (0..w * h)
+            .map(|i| (recovered.get((i % w) as u32, (i / w) as u32) as f64 - truth[i]).abs())
+            .fold(0.0, f64::max);
+        assert!(max_error < 0.05, "max error {max_error}");
+    }
+
+    #[test]
+    fn flat_normals_give_mid_grey() {
+        let normal = [128, 128, 255, 255].repeat(16);
+        let map = HeightMap::from_normal_map(&normal, 4, 4, BoundaryMode::Clamp);
+        assert!(map.data.iter().all(|&v| v == u16::MAX / 2 + 1));
+    }
+
+    #[test]
+    fn solve_reports_progress_and_stops_on_request() {
+        let normal = [128, 128, 255, 255].repeat(64 * 64);
+        let seen = std::sync::Mutex::new(Vec::new());
+        let record = |done: f32| {
+            seen.lock().unwrap().push(done);
+            true
+        };
+        assert!(HeightMap::recover(&normal, 64, 64, BoundaryMode::Wrap, &record).is_some());
+        let seen = seen.into_inner().unwrap();
+        assert_eq!(seen.first(), Some(&0.0));
+        assert!(seen.windows(2).all(|w| w[0] < w[1]) && seen.last() < Some(&1.0));
+
+        assert!(HeightMap::recover(&normal, 64, 64, BoundaryMode::Wrap, &|_| false).is_none());
+    }
+
+    #[test]
+    fn uploads_r16float_with_mips() {
+        let map = HeightMap {
+            data: vec![0, u16::MAX, u16::MAX, 0, 0, 0],
+            width: 3,
+            height: 2,
+        };
+        let image = map.into_image(ImageAddressMode::Repeat, RenderAssetUsages::MAIN_WORLD);
+        assert_eq!(image.texture_descriptor.format, TextureFormat::R16Float);
+        assert_eq!(image.texture_descriptor.mip_level_count, 2);
+        let data = image.data.expect("pixels");
+        // 3×2 then 1×1, two bytes each.
+        assert_eq!(data.len(), (6 + 1) * 2);
+        assert_eq!(&data[2..4], &0x3c00u16.to_le_bytes(), "1.0 as f16");
+        assert_eq!(&data[12..14], &0x3400u16.to_le_bytes(), "mean 0.25 as f16");
+    }
+
+    /// The upload budget's price for a height map is the exact image size.
+    #[test]
+    fn upload_len_matches_image_size() {
+        for (width, height) in [(1, 1), (3, 2), (64, 48), (256, 256), (100, 7)] {
+            let map = HeightMap {
+                data: vec![0; (width * height) as usize],
+                width,
+                height,
+            };
+            let len = map.upload_len();
+            let image = map.into_image(ImageAddressMode::Repeat, RenderAssetUsages::MAIN_WORLD);
+            assert_eq!(len, image.data.unwrap().len(), "{width}×{height}");
+        }
+    }
+
+    /// A generator's own field survives to 16-bit precision, and a fixed
+    /// range clamps instead of stretching.
+    #[test]
+    fn from_heights_keeps_the_exact_field() {
+        let (w, h) = (64, 48);
+        let field = periodic_field(w, h);
+        let truth = normalise(&field);
+        let map = HeightMap::from_heights(&field, w as u32, h as u32);
+        for (i, &t) in truth.iter().enumerate() {
+            let got = map.get((i % w) as u32, (i / w) as u32) as f64;
+            assert!((got - t).abs() < 1e-4, "texel {i}: {got} vs {t}");
+        }
+
+        let fixed =
+            HeightMap::from_heights_in_range(&[-2.0, 0.0, 0.5, 2.0], 2, 2, Some((0.0, 1.0)));
+        assert_eq!(fixed.data, [0, 0, u16::MAX / 2 + 1, u16::MAX]);
+    }
+}
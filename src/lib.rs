@@ -53,11 +53,61 @@
 //! offload work; the result lands on the entity as
 //! [`async_gen::TextureReady`].
 //!
+//! [`PendingTexture::cancel`](async_gen::PendingTexture::cancel) stops a
+//! task and reports [`TextureFailure::Cancelled`].  Built-in generators
+//! stop only after their generator call; an app-defined
+//! [`CancellableGenerator`] (see [`cancellable`]) stops between rows and
+//! reports its [`GenerationProgress`] row by row.
+//!
 //! # Procedural materials
 //! [`build_procedural_material_async`] is a one-shot helper that returns a
 //! `Handle<StandardMaterial>` immediately and patches the generated textures
 //! in once the background task completes.  Pair with an optional
 //! [`TextureCache`] resource to avoid regenerating identical configs.
+//! A positive [`MaterialSettings::parallax_depth_scale`] also binds the
+//! recovered height map as the material's `depth_map` for parallax relief.
+//! [`MaterialSettings::recommended_for`] gives per-generator PBR defaults
+//! (metallic metals, transmissive ice and glass, clearcoated marble,
+//! glowing lava) to start from; [`MaterialSettings::use_recommended`]
+//! applies them wherever settings become a material.
+//!
+//! # Height maps
+//! [`TextureConfig::spawn_with_height`] additionally reconstructs the
+//! height field behind the normal map as a [`HeightMap`], uploaded as an
+//! `R16Float` image in [`GeneratedHandles::height`] — for parallax
+//! mapping, displacement and height-based blending.  The built-in
+//! generators do not return their own field, so this is an approximation;
+//! a [`CancellableGenerator`] spawned with
+//! [`PendingTexture::cancellable_with_height`](async_gen::PendingTexture::cancellable_with_height)
+//! uploads its exact field instead.
+//!
+//! # Texture arrays
+//! [`PendingTextureArray`] generates a list of configs at one resolution
+//! into albedo / normal / ORM `texture_2d_array` images with per-layer mip
+//! chains, delivered as [`TextureArrayReady`] — the inputs of a terrain
+//! splat shader.  [`SplatMaterialPlugin`] supplies that shader:
+//! [`build_splat_material_async`] turns up to four [`SplatLayer`]s —
+//! [`MaterialSettings`] plus height / slope rules — into a
+//! [`SplatMaterial`] that blends them by rule or by an RGBA weight image.
+//!
+//! # Material files
+//! [`SymtexLoader`] loads `.symtex.ron` / `.symtex.json` files holding a
+//! serialized [`MaterialSettings`] into a textured `StandardMaterial`, with
+//! the generated images exposed as labeled sub-assets (`#albedo`,
+//! `#normal`, `#orm`, `#emissive`).  For live editing, load the same file
+//! as a `Handle<MaterialSettings>` and attach a [`ProceduralMaterialSource`]:
+//! edits on disk update the material in place and regenerate textures only
+//! when the texture config actually changed.
+//!
+//! # Exporting to disk
+//! [`export_png`] writes a [`TextureMap`] as standard PNG files — albedo
+//! and emissive tagged sRGB, normal and ORM tagged linear — optionally with
+//! one file per mip level, for art pipelines and tools outside Bevy.
+//! [`export_ktx2`] bakes the full mip chain into KTX2 containers for
+//! shipping builds that should do no generation at runtime.  Both are also
+//! methods on the map through [`TextureMapExport`].  The
+//! `symbios-bake` binary ([`bake`]) drives either exporter — or warms a
+//! [`FileStore`] cache — from a manifest of configs.
 //!
 //! # Animated parameters
 //! [`AnimatedProceduralMaterial`] drives time-varying texture parameters by
@@ -65,6 +115,14 @@
 //! fingerprint of the resulting [`TextureConfig`] changes (with a
 //! configurable wall-clock cooldown).
 //!
+//! # Texture LOD
+//! [`ProceduralMaterialLod`] regenerates a material at the resolution of the
+//! [`LodLevel`] matching its distance to the nearest camera, refining as the
+//! camera approaches and coarsening with hysteresis as it leaves; with a
+//! [`TextureCache`] every level is generated once.  [`LodMetric::ScreenSize`]
+//! picks the level from the projected pixel size instead, so field of view
+//! and window size count too.
+//!
 //! # Genetics
 //! All config types implement `symbios_genetics::Genotype` (see [`genetics`]),
 //! making them compatible with evolutionary search algorithms such as
@@ -82,31 +140,66 @@
 pub use symbios_texture::*;
 
 // Bevy-coupled modules kept in the wrapper.
+pub mod array;
 pub mod async_gen;
+pub mod bake;
 pub mod cache;
+pub mod cancellable;
 pub mod curve;
+pub mod export;
 pub mod generator;
+pub mod height;
+pub mod loader;
+pub mod lod;
 pub mod material;
+pub mod splat;
 
 #[cfg(feature = "egui")]
 pub mod ui;
 
-pub use async_gen::{AsyncTextureConfig, DEFAULT_POOL_THREADS};
+pub use array::{
+    PendingTextureArray, TextureArrayHandles, TextureArrayReady, maps_to_array_images,
+};
+pub use async_gen::{
+    AsyncTextureConfig, DEFAULT_POOL_THREADS, GenerationProgress, GenerationStage, TextureFailed,
+    TextureFailure, TexturePriority, TextureUploadSystems, UploadBudget, install_in_pool,
+    plan_texture_uploads, update_generation_progress,
+};
 pub use cache::{
-    DEFAULT_MEMORY_CACHE_ENTRIES, FileStore, MemoryStore, TextureCache, TextureCacheKey,
-    TextureCacheStore,
+    BlobCompression, CacheDiagnostics, CacheStats, DEFAULT_MEMORY_CACHE_ENTRIES, FileStore,
+    KindStats, MemoryStore, PixelLoader, PruneSummary, TextureCache, TextureCacheKey,
+    TextureCacheStore, measure_cache_diagnostics,
+};
+pub use cancellable::{
+    CancellableGenerator, GenerationContext, generate_surface_cancellable,
+    generate_surface_emissive_cancellable,
 };
 pub use curve::{
     AnimatedProceduralMaterial, EaseInOut, Linear, ParameterCurve, ScriptedFn, Stepped,
     TextureCurve,
 };
+pub use export::{
+    ExportColorSpace, ExportError, KTX2_ADDRESS_MODE_KEY, Ktx2ExportOptions, PngExportOptions,
+    TextureMapExport, encode_ktx2, encode_png, export_ktx2, export_png, ktx2_address_mode,
+};
 pub use generator::{
     GeneratedHandles, TextureError, TextureGenerator, TextureMap, Workspace, map_to_images,
     map_to_images_card, map_to_images_card_with_usages, map_to_images_with_usages,
 };
+pub use height::HeightMap;
+pub use loader::{
+    MaterialSettingsLoader, ProceduralMaterialSource, SymtexLoader, SymtexLoaderSettings,
+    sync_procedural_material_sources,
+};
+pub use lod::{LodLevel, LodMetric, ProceduralMaterialLod, update_procedural_material_lods};
 pub use material::{
-    MaterialSettings, PatchMaterialTextures, RenderProperties, TextureConfig,
-    build_procedural_material_async,
+    MaterialSettings, MaterialTexturesFailed, MaterialTexturesPatched, ParallaxMethod,
+    PatchMaterialTextures, PatchesMaterial, RenderProperties, TextureConfig, TextureSlots,
+    build_procedural_material_async, request_procedural_textures,
+};
+pub use splat::{
+    MAX_SPLAT_LAYERS, SplatExtension, SplatLayer, SplatMaterial, SplatMaterialPlugin, SplatRule,
+    SplatTexturesFailed, SplatWeights, build_splat_material_async,
 };
 pub use symbios_texture::leaf::{LeafConfig, LeafGenerator, LeafSample, LeafSampler, sample_leaf};
 pub use symbios_texture::noise::ToroidalNoise;
@@ -114,17 +207,24 @@ pub use symbios_texture::sprite::{CellRng, SpriteCell, SpriteSample, generate_at
 pub use symbios_texture::surface::{SurfaceCell, SurfaceSample, generate_surface};
 pub use symbios_texture::twig::{TwigConfig, TwigGenerator};
 
+use bevy::diagnostic::RegisterDiagnostic;
 use bevy::prelude::*;
 
 /// Bevy plugin — registers the async-generation polling system and applies
 /// the [`AsyncTextureConfig`] to the private texture-generation thread pool.
 ///
+/// It also registers the [`CacheDiagnostics`] measurements, fed from the
+/// [`TextureCache`] resource when one is present.
+///
 /// Construct via [`Default`] for the standard [`DEFAULT_POOL_THREADS`] cap,
 /// or set [`SymbiosTexturePlugin::config`] explicitly for custom pool sizing:
 ///
 /// ```rust,ignore
 /// app.add_plugins(SymbiosTexturePlugin {
-///     config: AsyncTextureConfig { pool_threads: 0 }, // auto = cores / 2
+///     config: AsyncTextureConfig {
+///         pool_threads: 0,                   // auto = cores / 2
+///         upload_budget_bytes: Some(8 << 20), // at most 8 MiB of uploads per frame
+///     },
 /// });
 /// ```
 ///
@@ -144,12 +244,87 @@ impl Plugin for SymbiosTexturePlugin {
         // ran first).  The first call wins; documented above.
         let _ = async_gen::set_pool_config(self.config.clone());
         app.insert_resource(self.config.clone());
+        app.insert_resource(UploadBudget::new(self.config.upload_budget_bytes));
+        app.add_systems(First, async_gen::reset_upload_budget);
+        for diagnostic in CacheDiagnostics::diagnostics() {
+            app.register_diagnostic(diagnostic);
+        }
+        app.configure_sets(
+            Update,
+            (TextureUploadSystems::Plan, TextureUploadSystems::Upload).chain(),
+        );
+        app.add_systems(
+            Update,
+            (
+                (
+                    async_gen::update_generation_progress,
+                    async_gen::plan_texture_uploads,
+                )
+                    .in_set(TextureUploadSystems::Plan),
+                (
+                    async_gen::poll_texture_tasks,
+                    array::poll_texture_array_tasks,
+                    material::patch_procedural_material_textures::<StandardMaterial>,
+                )
+                    .in_set(TextureUploadSystems::Upload),
+                curve::tick_animated_procedural_materials::<StandardMaterial>,
+                lod::update_procedural_material_lods::<StandardMaterial>,
+                cache::measure_cache_diagnostics.after(TextureUploadSystems::Upload),
+            ),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Materials whose emission waits for its glow map bind this
+        // placeholder until the map lands; see `MaterialSettings::emission_needs_map`.
+        if let Some(mut images) = app.world_mut().get_resource_mut::<Assets<Image>>() {
+            material::register_emissive_placeholder(&mut images);
+        }
+        // Registered in `finish` rather than `build` so the plugin works
+        // regardless of whether it is added before or after `AssetPlugin`;
+        // headless apps without an `AssetServer` simply skip the loader.
+        if app.world().contains_resource::<bevy::asset::AssetServer>() {
+            // `SymtexLoader` goes last so untyped and labeled loads of
+            // `.symtex` paths resolve to the material loader.
+            app.init_asset::<MaterialSettings>()
+                .register_asset_loader(MaterialSettingsLoader)
+                .register_asset_loader(SymtexLoader)
+                .add_systems(Update, sync_procedural_material_sources);
+        }
+    }
+}
+
+/// Feeds procedural textures into a material type other than
+/// `StandardMaterial` — an `ExtendedMaterial` or an app's own
+/// [`TextureSlots`] type — by registering the patch, animation and LOD
+/// systems for `M`:
+///
+/// ```rust,ignore
+/// app.add_plugins((
+///     SymbiosTexturePlugin::default(),
+///     ProceduralMaterialPlugin::<ExtendedMaterial<StandardMaterial, Outline>>::default(),
+/// ));
+/// ```
+///
+/// Requires [`SymbiosTexturePlugin`], which already covers
+/// `StandardMaterial` itself.
+pub struct ProceduralMaterialPlugin<M: TextureSlots>(std::marker::PhantomData<M>);
+
+impl<M: TextureSlots> Default for ProceduralMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<M: TextureSlots> Plugin for ProceduralMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
             (
-                async_gen::poll_texture_tasks,
-                material::patch_procedural_material_textures,
-                curve::tick_animated_procedural_materials,
+                material::patch_procedural_material_textures::<M>
+                    .in_set(TextureUploadSystems::Upload),
+                curve::tick_animated_procedural_materials::<M>,
+                lod::update_procedural_material_lods::<M>,
             ),
         );
     }
@@ -0,0 +1,700 @@
+//! Asset loader for serialized [`MaterialSettings`] files.
+//!
+//! Materials kept as data (`bark.symtex.ron`, `bricks.symtex.json`) load
+//! through the regular [`AssetServer`](bevy::asset::AssetServer) like any
+//! other asset:
+//!
+//! ```rust,ignore
+//! let bark: Handle<StandardMaterial> = asset_server.load("bark.symtex.ron");
+//! let albedo: Handle<Image> = asset_server.load("bark.symtex.ron#albedo");
+//! ```
+//!
+//! The file body is a [`MaterialSettings`] value.  The embedded
+//! [`TextureConfig`] is internally tagged
+//! (`"$type"`), which RON can only express with map syntax:
+//!
+//! ```ron
+//! (
+//!     base_color: (0.6, 0.4, 0.2),
+//!     emission_color: (0.0, 0.0, 0.0),
+//!     emission_strength: 0.0,
+//!     roughness: 0.9,
+//!     metallic: 0.0,
+//!     uv_scale: 2.0,
+//!     texture: {
+//!         "$type": "Brick",
+//!         "seed": 42,
+//!         "scale": 4.0,
+//!         // … remaining BrickConfig fields …
+//!     },
+//! )
+//! ```
+//!
+//! Fields after `uv_scale` — UV offset / rotation, reflectance, clearcoat,
+//! anisotropy, transmission, parallax — are optional and default to
+//! `StandardMaterial`'s values, so an ice file adds only what it changes
+//! (`specular_transmission: 0.9, ior: 1.31, …`).  With
+//! `use_recommended: true` the PBR factors come from
+//! [`MaterialSettings::recommended_for`] instead (see
+//! [`use_recommended`](MaterialSettings::use_recommended)).
+//!
+//! [`SymtexLoader`] produces the [`StandardMaterial`] as the root asset and
+//! registers the generated images as labeled sub-assets — `albedo`,
+//! `normal`, `orm`, `emissive` (only for generators that produce a glow
+//! map), and `height` (only when
+//! [`parallax_depth_scale`](MaterialSettings::parallax_depth_scale) is
+//! positive; bound as the material's `depth_map`).  Generation runs on the private texture-generation pool (see
+//! [`AsyncTextureConfig`](crate::AsyncTextureConfig)), so a slow bake never
+//! blocks the asset I/O threads.  Resolution comes from
+//! [`SymtexLoaderSettings`] (default [`DEFAULT_SYMTEX_SIZE`]²), settable
+//! per file through a `.meta` file or `AssetServer::load_with_settings`.
+//!
+//! The loader does not consult [`TextureCache`] — Bevy's asset server
+//! already deduplicates loads of the same path.
+//!
+//! # Hot reload
+//!
+//! The same files also load as plain `Handle<MaterialSettings>` assets
+//! through [`MaterialSettingsLoader`].  Attach a [`ProceduralMaterialSource`]
+//! to keep a material in sync with one: every time the file changes on disk
+//! (with Bevy's `file_watcher` feature enabled), the material behind the
+//! *same* `Handle<StandardMaterial>` picks up the new PBR factors
+//! immediately, and the textures regenerate through the regular
+//! [`patch_procedural_material_textures`] flow — or not at all when the
+//! texture config's fingerprint did not change or a [`TextureCache`] already
+//! holds it:
+//!
+//! ```rust,ignore
+//! let material = materials.add(StandardMaterial::default());
+//! commands.spawn((
+//!     Mesh3d(mesh),
+//!     MeshMaterial3d(material.clone()),
+//!     ProceduralMaterialSource::new(asset_server.load("bricks.symtex.ron"), material, 512, 512),
+//! ));
+//! ```
+//!
+//! [`patch_procedural_material_textures`]: crate::material::patch_procedural_material_textures
+//! [`TextureCache`]: crate::cache::TextureCache
+
+use std::collections::HashSet;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{
+    AssetEvent, AssetId, AssetLoader, Assets, Handle, LoadContext, RenderAssetUsages,
+};
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::image::{Image, ImageAddressMode};
+use bevy::pbr::StandardMaterial;
+use bevy::reflect::TypePath;
+
+use crate::async_gen::run_on_pool;
+use crate::cache::TextureCache;
+use crate::generator::{ImageSet, TextureError};
+use crate::height::HeightMap;
+use crate::material::{
+    MaterialSettings, TextureConfig, apply_emissive_map, request_material_textures,
+};
+use crate::normal::BoundaryMode;
+
+/// Default texture resolution (per side) used when a `.symtex` file is
+/// loaded without explicit [`SymtexLoaderSettings`].
+pub const DEFAULT_SYMTEX_SIZE: u32 = 512;
+
+/// File extensions claimed by [`SymtexLoader`].
+pub const SYMTEX_EXTENSIONS: &[&str] = &["symtex.ron", "symtex.json"];
+
+/// Per-load settings for [`SymtexLoader`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SymtexLoaderSettings {
+    /// Generated texture width in texels.
+    pub width: u32,
+    /// Generated texture height in texels.
+    pub height: u32,
+}
+
+impl Default for SymtexLoaderSettings {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_SYMTEX_SIZE,
+            height: DEFAULT_SYMTEX_SIZE,
+        }
+    }
+}
+
+/// Errors raised while loading a `.symtex` file.
+#[derive(Debug)]
+pub enum SymtexLoadError {
+    /// The asset reader failed.
+    Io(std::io::Error),
+    /// The file is not a valid RON [`MaterialSettings`].
+    Ron(ron::error::SpannedError),
+    /// The file is not a valid JSON [`MaterialSettings`].
+    Json(serde_json::Error),
+    /// The generator rejected the requested dimensions.
+    Texture(TextureError),
+    /// The generator panicked on the worker thread.
+    Panicked,
+}
+
+impl std::fmt::Display for SymtexLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not read symtex file: {e}"),
+            Self::Ron(e) => write!(f, "invalid symtex RON: {e}"),
+            Self::Json(e) => write!(f, "invalid symtex JSON: {e}"),
+            Self::Texture(e) => write!(f, "symtex texture generation failed: {e}"),
+            Self::Panicked => f.write_str("symtex texture generation panicked"),
+        }
+    }
+}
+
+impl std::error::Error for SymtexLoadError {}
+
+impl From<std::io::Error> for SymtexLoadError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Parse a `.symtex` body, choosing JSON or RON by the file name.
+///
+/// Anything not ending in `.json` is read as RON, which is also what a
+/// `.symtex` file loaded under a non-matching extension falls back to.
+pub fn parse_material_settings(
+    bytes: &[u8],
+    path: &std::path::Path,
+) -> Result<MaterialSettings, SymtexLoadError> {
+    let is_json = path.extension().is_some_and(|ext| ext == "json");
+    if is_json {
+        serde_json::from_slice(bytes).map_err(SymtexLoadError::Json)
+    } else {
+        ron::de::from_bytes(bytes).map_err(SymtexLoadError::Ron)
+    }
+}
+
+/// [`AssetLoader`] turning `.symtex.ron` / `.symtex.json` files into a
+/// textured [`StandardMaterial`].
+///
+/// Registered automatically by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) when an
+/// `AssetServer` is present.
+#[derive(Default, TypePath)]
+pub struct SymtexLoader;
+
+impl AssetLoader for SymtexLoader {
+    type Asset = StandardMaterial;
+    type Settings = SymtexLoaderSettings;
+    type Error = SymtexLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &SymtexLoaderSettings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<StandardMaterial, SymtexLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let material_settings = parse_material_settings(&bytes, load_context.path().path())?;
+
+        let mut material = material_settings.to_standard_material();
+        let config = material_settings.texture.clone();
+        let is_card = config.render_properties().is_card;
+        let with_height = material_settings.uses_parallax();
+        let (width, height) = (settings.width, settings.height);
+        let generated = run_on_pool(move || {
+            config.generate_sync(width, height).map(|result| {
+                result.map(|map| {
+                    let boundary = if is_card {
+                        BoundaryMode::Clamp
+                    } else {
+                        BoundaryMode::Wrap
+                    };
+                    let height_map =
+                        with_height.then(|| HeightMap::from_texture_map(&map, boundary));
+                    (map.with_mips(), height_map)
+                })
+            })
+        })
+        .await
+        .ok_or(SymtexLoadError::Panicked)?;
+
+        // `TextureConfig::None` generates nothing: the material keeps its
+        // plain PBR factors and no sub-assets are registered.
+        if let Some(result) = generated {
+            let (map, height_map) = result.map_err(SymtexLoadError::Texture)?;
+            let address_mode = if is_card {
+                ImageAddressMode::ClampToEdge
+            } else {
+                ImageAddressMode::Repeat
+            };
+            material.depth_map = height_map.map(|height_map| {
+                load_context.add_labeled_asset(
+                    "height".into(),
+                    height_map.into_image(address_mode, RenderAssetUsages::RENDER_WORLD),
+                )
+            });
+            let set = ImageSet::from_map(map, address_mode, RenderAssetUsages::RENDER_WORLD);
+            material.base_color_texture =
+                Some(load_context.add_labeled_asset("albedo".into(), set.albedo));
+            material.normal_map_texture =
+                Some(load_context.add_labeled_asset("normal".into(), set.normal));
+            material.metallic_roughness_texture =
+                Some(load_context.add_labeled_asset("orm".into(), set.roughness));
+            let emissive = set
+                .emissive
+                .map(|image| load_context.add_labeled_asset("emissive".into(), image));
+            apply_emissive_map(&mut material, emissive);
+        }
+
+        Ok(material)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        SYMTEX_EXTENSIONS
+    }
+}
+
+/// [`AssetLoader`] reading `.symtex` files as plain [`MaterialSettings`]
+/// assets — the source side of [`ProceduralMaterialSource`] hot reload.
+///
+/// Shares its extensions with [`SymtexLoader`]; the asset server picks the
+/// loader by the requested handle type.  Untyped and labeled loads resolve
+/// to [`SymtexLoader`], which the plugin registers last.
+#[derive(Default, TypePath)]
+pub struct MaterialSettingsLoader;
+
+impl AssetLoader for MaterialSettingsLoader {
+    type Asset = MaterialSettings;
+    type Settings = ();
+    type Error = SymtexLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<MaterialSettings, SymtexLoadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        parse_material_settings(&bytes, load_context.path().path())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        SYMTEX_EXTENSIONS
+    }
+}
+
+/// Keeps `material` in sync with a (hot-reloadable) [`MaterialSettings`]
+/// asset.
+///
+/// Consumed by [`sync_procedural_material_sources`].  The material handle
+/// keeps its identity across reloads — only the asset behind it changes —
+/// so meshes referencing it never need re-pointing.
+#[derive(Component)]
+pub struct ProceduralMaterialSource {
+    /// Settings asset to follow, typically `asset_server.load("x.symtex.ron")`.
+    pub settings: Handle<MaterialSettings>,
+    /// Material whose factors and texture slots are rewritten on change.
+    pub material: Handle<StandardMaterial>,
+    /// Texture width (texels) for every regeneration.
+    pub width: u32,
+    /// Texture height (texels) for every regeneration.
+    pub height: u32,
+    /// Fingerprint of the texture config last applied; `None` until the
+    /// settings asset first loads.
+    applied_fingerprint: Option<u64>,
+    /// Whether a height map is bound from the last applied settings, so
+    /// turning parallax on regenerates even when the texture config is
+    /// unchanged.
+    applied_height: bool,
+    /// Patch task dispatched for the latest config, if still running.
+    in_flight: Option<Entity>,
+}
+
+impl ProceduralMaterialSource {
+    /// Follow `settings`, writing into `material` at `width × height`.
+    pub fn new(
+        settings: Handle<MaterialSettings>,
+        material: Handle<StandardMaterial>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            settings,
+            material,
+            width,
+            height,
+            applied_fingerprint: None,
+            applied_height: false,
+            in_flight: None,
+        }
+    }
+}
+
+/// Bevy system — applies newly loaded or modified [`MaterialSettings`]
+/// assets to every [`ProceduralMaterialSource`] that follows them.
+///
+/// PBR factors, UV transform, parallax parameters and render flags update in
+/// place immediately; the current textures stay bound until their
+/// replacements land.  Texture regeneration is skipped when the config
+/// fingerprint is unchanged and no height map is newly required (a
+/// roughness tweak never re-bakes), served synchronously from a memory
+/// [`TextureCache`] hit, and otherwise dispatched as a
+/// [`PatchMaterialTextures`](crate::material::PatchMaterialTextures) task —
+/// superseding (and thereby cancelling) any task still running for an
+/// earlier revision of the file.
+///
+/// Registered automatically by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) when an
+/// `AssetServer` is present.
+#[allow(clippy::too_many_arguments)]
+pub fn sync_procedural_material_sources(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<MaterialSettings>>,
+    settings_assets: Res<Assets<MaterialSettings>>,
+    mut sources: Query<&mut ProceduralMaterialSource>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    cache: Option<Res<TextureCache>>,
+) {
+    let changed: HashSet<AssetId<MaterialSettings>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for mut source in &mut sources {
+        let id = source.settings.id();
+        if source.applied_fingerprint.is_some() && !changed.contains(&id) {
+            continue;
+        }
+        let Some(settings) = settings_assets.get(id) else {
+            continue;
+        };
+
+        let textured = !matches!(settings.texture, TextureConfig::None);
+        let with_height = settings.uses_parallax();
+        if let Some(material) = materials.get_mut(&source.material) {
+            let mut fresh = settings.to_standard_material();
+            // Carry the current textures over so the surface does not flash
+            // untextured while a regeneration is in flight.
+            if textured {
+                fresh.base_color_texture = material.base_color_texture.take();
+                fresh.normal_map_texture = material.normal_map_texture.take();
+                fresh.metallic_roughness_texture = material.metallic_roughness_texture.take();
+                apply_emissive_map(&mut fresh, material.emissive_texture.take());
+                if with_height {
+                    fresh.depth_map = material.depth_map.take();
+                }
+            }
+            *material = fresh;
+        }
+
+        let fingerprint = settings.texture.fingerprint();
+        let up_to_date = source.applied_fingerprint == Some(fingerprint)
+            && (source.applied_height || !with_height);
+        // Dropping parallax unbinds the height map above; bringing it back
+        // later must regenerate.
+        source.applied_height &= with_height;
+        if up_to_date {
+            continue;
+        }
+        if let Some(previous) = source.in_flight.take() {
+            commands.entity(previous).try_despawn();
+        }
+        source.in_flight = request_material_textures(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            cache.as_deref(),
+            &settings.texture,
+            &source.material,
+            source.width,
+            source.height,
+            with_height,
+            settings.preview_size,
+        );
+        source.applied_fingerprint = Some(fingerprint);
+        source.applied_height = with_height;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    use bevy::asset::{AssetApp, AssetPlugin, AssetServer, Assets, Handle};
+    use bevy::image::Image;
+    use bevy::prelude::{App, MinimalPlugins};
+
+    use crate::brick::BrickConfig;
+    use crate::material::TextureConfig;
+
+    fn brick_settings() -> MaterialSettings {
+        MaterialSettings {
+            roughness: 0.9,
+            texture: TextureConfig::Brick(BrickConfig::default()),
+            ..MaterialSettings::default()
+        }
+    }
+
+    /// RON files spell the internally tagged texture config with map syntax.
+    const BRICK_RON: &str = r#"(
+        base_color: (0.6, 0.4, 0.2),
+        emission_color: (0.0, 0.0, 0.0),
+        emission_strength: 0.0,
+        roughness: 0.9,
+        metallic: 0.0,
+        uv_scale: 2.0,
+        texture: {
+            "$type": "Brick",
+            "seed": 7,
+            "scale": 4.0,
+            "row_offset": 0.5,
+            "aspect_ratio": 2.0,
+            "mortar_size": 0.05,
+            "bevel": 0.5,
+            "cell_variance": 0.15,
+            "roughness": 0.5,
+            "color_brick": (0.56, 0.28, 0.18),
+            "color_mortar": (0.76, 0.73, 0.67),
+            "normal_strength": 4.0,
+        },
+    )"#;
+
+    #[test]
+    fn parses_ron_and_json_by_extension() {
+        let ron = parse_material_settings(BRICK_RON.as_bytes(), Path::new("a.symtex.ron"))
+            .expect("RON parses");
+        assert!(matches!(&ron.texture, TextureConfig::Brick(b) if b.seed == 7));
+        assert_eq!(ron.uv_scale, 2.0);
+
+        let settings = brick_settings();
+        let json = serde_json::to_vec(&settings).expect("serialise");
+        let parsed =
+            parse_material_settings(&json, Path::new("a.symtex.json")).expect("JSON parses");
+        assert_eq!(parsed.texture.fingerprint(), settings.texture.fingerprint());
+
+        assert!(matches!(
+            parse_material_settings(b"not json", Path::new("a.symtex.json")),
+            Err(SymtexLoadError::Json(_))
+        ));
+    }
+
+    /// Files written before the extended PBR fields existed still load,
+    /// with `StandardMaterial`'s defaults; files that set them round-trip.
+    #[test]
+    fn extended_pbr_fields_default_when_omitted() {
+        let ron = parse_material_settings(BRICK_RON.as_bytes(), Path::new("a.symtex.ron"))
+            .expect("RON parses");
+        let fresh = ron.to_standard_material();
+        let standard = StandardMaterial::default();
+        assert_eq!(fresh.reflectance, standard.reflectance);
+        assert_eq!(
+            fresh.clearcoat_perceptual_roughness,
+            standard.clearcoat_perceptual_roughness
+        );
+        assert_eq!(fresh.ior, standard.ior);
+        assert_eq!(ron.uv_offset, [0.0, 0.0]);
+        assert_eq!(ron.specular_transmission, 0.0);
+
+        let ice = MaterialSettings {
+            specular_transmission: 0.9,
+            thickness: 0.2,
+            ior: 1.31,
+            clearcoat: 1.0,
+            ..brick_settings()
+        };
+        let json = serde_json::to_vec(&ice).expect("serialise");
+        let parsed =
+            parse_material_settings(&json, Path::new("a.symtex.json")).expect("JSON parses");
+        assert_eq!(parsed.specular_transmission, 0.9);
+        assert_eq!(parsed.thickness, 0.2);
+        assert_eq!(parsed.ior, 1.31);
+        assert_eq!(parsed.clearcoat, 1.0);
+    }
+
+    /// End-to-end through a real `AssetServer`: the root asset is a textured
+    /// `StandardMaterial` and the images resolve as labeled sub-assets.
+    #[test]
+    fn loads_material_with_labeled_images() {
+        let dir = std::env::temp_dir().join(format!("bst-symtex-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create asset dir");
+        std::fs::write(dir.join("brick.symtex.ron"), BRICK_RON).expect("write asset");
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: dir.to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<Image>()
+        .init_asset::<StandardMaterial>()
+        .register_asset_loader(SymtexLoader);
+
+        let handle: Handle<StandardMaterial> = app
+            .world()
+            .resource::<AssetServer>()
+            .load_with_settings("brick.symtex.ron", |s: &mut SymtexLoaderSettings| {
+                s.width = 16;
+                s.height = 16;
+            });
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while app
+            .world()
+            .resource::<Assets<StandardMaterial>>()
+            .get(&handle)
+            .is_none()
+        {
+            assert!(Instant::now() < deadline, "symtex load timed out");
+            app.update();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let material = app
+            .world()
+            .resource::<Assets<StandardMaterial>>()
+            .get(&handle)
+            .expect("material loaded");
+        assert_eq!(material.perceptual_roughness, 0.9);
+        let albedo = material.base_color_texture.clone().expect("albedo slot");
+        assert!(material.normal_map_texture.is_some());
+        assert!(material.metallic_roughness_texture.is_some());
+        assert!(material.emissive_texture.is_none(), "brick has no glow map");
+
+        let labeled: Handle<Image> = app
+            .world()
+            .resource::<AssetServer>()
+            .get_handle("brick.symtex.ron#albedo")
+            .expect("albedo registered as a labeled sub-asset");
+        assert_eq!(labeled.id(), albedo.id());
+        let image = app
+            .world()
+            .resource::<Assets<Image>>()
+            .get(&albedo)
+            .expect("albedo image loaded");
+        assert_eq!(image.texture_descriptor.size.width, 16);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Drive `sync_procedural_material_sources` with real asset events:
+    /// scalar edits apply in place without re-baking, texture edits
+    /// regenerate, and reverting to an earlier config is a cache hit.
+    #[test]
+    fn source_follows_settings_edits() {
+        use crate::async_gen::poll_texture_tasks;
+        use crate::material::{PatchMaterialTextures, patch_procedural_material_textures};
+        use bevy::prelude::{Update, With};
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<StandardMaterial>()
+            .init_asset::<MaterialSettings>()
+            .insert_resource(TextureCache::memory(8))
+            .add_systems(
+                Update,
+                (
+                    sync_procedural_material_sources,
+                    poll_texture_tasks,
+                    patch_procedural_material_textures::<StandardMaterial>,
+                ),
+            );
+
+        let settings = app
+            .world_mut()
+            .resource_mut::<Assets<MaterialSettings>>()
+            .add(brick_settings());
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        app.world_mut().spawn(ProceduralMaterialSource::new(
+            settings.clone(),
+            material.clone(),
+            16,
+            16,
+        ));
+
+        let run_until_patched = |app: &mut App| {
+            let deadline = Instant::now() + Duration::from_secs(30);
+            loop {
+                app.update();
+                let pending = app
+                    .world_mut()
+                    .query_filtered::<(), With<PatchMaterialTextures>>()
+                    .iter(app.world())
+                    .count();
+                if pending == 0 {
+                    break;
+                }
+                assert!(Instant::now() < deadline, "texture patch timed out");
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        };
+        let albedo_of = |app: &App| {
+            app.world()
+                .resource::<Assets<StandardMaterial>>()
+                .get(&material)
+                .and_then(|m| m.base_color_texture.clone())
+                .expect("albedo bound")
+        };
+        let edit = |app: &mut App, f: &dyn Fn(&mut MaterialSettings)| {
+            let mut assets = app.world_mut().resource_mut::<Assets<MaterialSettings>>();
+            f(assets.get_mut(&settings).expect("settings asset"));
+            // Asset events flush in `PostUpdate`; the sync system sees them
+            // on the following frame.
+            app.update();
+        };
+
+        run_until_patched(&mut app);
+        let first = albedo_of(&app);
+
+        // Scalar-only edit: factors update, textures untouched, no task.
+        edit(&mut app, &|s| s.roughness = 0.25);
+        app.update();
+        let m = app.world().resource::<Assets<StandardMaterial>>();
+        assert_eq!(m.get(&material).unwrap().perceptual_roughness, 0.25);
+        assert_eq!(albedo_of(&app).id(), first.id());
+        let pending = app
+            .world_mut()
+            .query::<&PatchMaterialTextures>()
+            .iter(app.world())
+            .count();
+        assert_eq!(pending, 0, "scalar edit must not regenerate");
+
+        // Texture edit: regenerates into the same material handle.
+        edit(&mut app, &|s| {
+            if let TextureConfig::Brick(b) = &mut s.texture {
+                b.seed += 1;
+            }
+        });
+        run_until_patched(&mut app);
+        let second = albedo_of(&app);
+        assert_ne!(second.id(), first.id());
+
+        // Reverting is served synchronously from the cache.
+        edit(&mut app, &|s| {
+            if let TextureConfig::Brick(b) = &mut s.texture {
+                b.seed -= 1;
+            }
+        });
+        app.update();
+        assert_eq!(albedo_of(&app).id(), first.id());
+    }
+}
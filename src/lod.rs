@@ -0,0 +1,442 @@
+//! Distance- or screen-size-driven texture resolution for procedural
+//! materials.
+//!
+//! A world full of procedural materials cannot afford a full-resolution bake
+//! of every one.  Attach a [`ProceduralMaterialLod`] to the entity whose
+//! position stands for the material (usually the mesh using it) and
+//! [`update_procedural_material_lods`] picks the generation resolution from
+//! the distance to the nearest active camera:
+//!
+//! ```rust,ignore
+//! let material = materials.add(settings.to_standard_material());
+//! commands.spawn((
+//!     Mesh3d(mesh),
+//!     MeshMaterial3d(material.clone()),
+//!     Transform::from_xyz(0.0, 0.0, -40.0),
+//!     ProceduralMaterialLod::new(
+//!         settings,
+//!         material,
+//!         [
+//!             LodLevel { max_distance: 15.0, resolution: 1024 },
+//!             LodLevel { max_distance: 50.0, resolution: 256 },
+//!             LodLevel { max_distance: f32::INFINITY, resolution: 64 },
+//!         ],
+//!     ),
+//! ));
+//! ```
+//!
+//! Approaching a material regenerates it at the finer level as soon as the
+//! camera crosses that level's `max_distance`; leaving only drops back once
+//! the camera is [`hysteresis`](ProceduralMaterialLod::hysteresis) beyond
+//! it, so a player pacing along the boundary does not flip-flop bakes.  The
+//! current textures stay bound until the replacement lands.
+//!
+//! Distance alone ignores field of view, zoom and window size.  With
+//! [`LodMetric::ScreenSize`] the level is instead picked from how many
+//! pixels tall a bounding sphere around the entity appears, projected
+//! through each camera's [`Projection`] and viewport: the coarsest level
+//! whose `resolution` still covers that height wins, so a telephoto camera
+//! or a 4K window refines what a wide-angle camera would leave coarse.
+//!
+//! ```rust,ignore
+//! ProceduralMaterialLod::new(settings, material, levels)
+//!     .with_metric(LodMetric::ScreenSize { radius: 2.0 })
+//! ```
+//!
+//! Every level goes through the usual [`TextureCache`] lookup under its own
+//! [`TextureCacheKey`](crate::cache::TextureCacheKey) resolution, so with a
+//! cache present revisiting a level is served from memory (or disk) instead
+//! of regenerating.
+
+use bevy::asset::Assets;
+use bevy::camera::Projection;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
+use bevy::image::Image;
+use bevy::math::{Mat4, Vec3};
+use bevy::pbr::StandardMaterial;
+use bevy::prelude::{Camera, GlobalTransform, Handle};
+
+use crate::cache::TextureCache;
+use crate::material::{MaterialSettings, TextureSlots, request_material_textures};
+
+/// One rung of a [`ProceduralMaterialLod`] ladder.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LodLevel {
+    /// Farthest camera distance (world units) at which this level is used.
+    /// Beyond the last level's distance the last level still applies.
+    /// Ignored under [`LodMetric::ScreenSize`].
+    pub max_distance: f32,
+    /// Width and height (texels) generated for this level.
+    pub resolution: u32,
+}
+
+/// What a [`ProceduralMaterialLod`] measures to pick its level.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LodMetric {
+    /// Distance to the nearest active camera, compared against each
+    /// level's [`max_distance`](LodLevel::max_distance).
+    #[default]
+    Distance,
+    /// Projected height, in physical pixels, of a sphere of `radius` world
+    /// units around the entity, taken from the active camera that shows it
+    /// largest.  The level with the smallest
+    /// [`resolution`](LodLevel::resolution) at least that tall is used, or
+    /// the finest level when none is.  Cameras without a [`Projection`] or
+    /// a computed viewport are skipped.
+    ScreenSize {
+        /// Bounding radius of what the material covers, in world units.
+        radius: f32,
+    },
+}
+
+/// Regenerates `material`'s textures at a resolution chosen from the
+/// distance between this entity's [`GlobalTransform`] and the nearest
+/// active camera, or from its projected size (see [`LodMetric`]).  `M` is any [`TextureSlots`] material, by default
+/// [`StandardMaterial`].
+///
+/// Consumed by [`update_procedural_material_lods`].  See the
+/// [module docs](self) for an example.
+#[derive(Component)]
+pub struct ProceduralMaterialLod<M: TextureSlots = StandardMaterial> {
+    /// Settings whose [`texture`](MaterialSettings::texture) is generated
+    /// at each level.  Editing the texture config regenerates at the current
+    /// level; parallax settings also bake the height map.
+    pub settings: MaterialSettings,
+    /// Material whose texture slots are patched.
+    pub material: Handle<M>,
+    /// Fraction of a level's `max_distance` the camera must move beyond it
+    /// before a coarser level is chosen — under [`LodMetric::ScreenSize`],
+    /// the fraction the projected size must shrink below a coarser level's
+    /// `resolution`.  Default
+    /// [`DEFAULT_HYSTERESIS`](Self::DEFAULT_HYSTERESIS).
+    pub hysteresis: f32,
+    /// What picks the level.  Default [`LodMetric::Distance`].
+    pub metric: LodMetric,
+    /// Levels sorted by ascending `max_distance`.
+    levels: Vec<LodLevel>,
+    /// Level index and texture fingerprint last dispatched or applied.
+    applied: Option<(usize, u64)>,
+    /// Patch task for the latest level, if still running.
+    in_flight: Option<Entity>,
+}
+
+impl<M: TextureSlots> ProceduralMaterialLod<M> {
+    /// Default [`hysteresis`](Self::hysteresis): drop a level 15% past its
+    /// boundary.
+    pub const DEFAULT_HYSTERESIS: f32 = 0.15;
+
+    /// Generate `settings` into `material` at the `levels` resolutions, in
+    /// any order.
+    ///
+    /// # Panics
+    /// If `levels` is empty.
+    pub fn new(
+        settings: MaterialSettings,
+        material: Handle<M>,
+        levels: impl IntoIterator<Item = LodLevel>,
+    ) -> Self {
+        let mut levels: Vec<LodLevel> = levels.into_iter().collect();
+        assert!(!levels.is_empty(), "ProceduralMaterialLod needs a level");
+        levels.sort_by(|a, b| a.max_distance.total_cmp(&b.max_distance));
+        Self {
+            settings,
+            material,
+            hysteresis: Self::DEFAULT_HYSTERESIS,
+            metric: LodMetric::Distance,
+            levels,
+            applied: None,
+            in_flight: None,
+        }
+    }
+
+    /// Sets [`hysteresis`](Self::hysteresis) and returns `self` for
+    /// chaining.
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis.max(0.0);
+        self
+    }
+
+    /// Sets [`metric`](Self::metric) and returns `self` for chaining.
+    pub fn with_metric(mut self, metric: LodMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// The levels, nearest first.
+    pub fn levels(&self) -> &[LodLevel] {
+        &self.levels
+    }
+
+    /// The level last requested, or `None` before the first camera was
+    /// seen.
+    pub fn current_level(&self) -> Option<LodLevel> {
+        self.applied.map(|(level, _)| self.levels[level])
+    }
+
+    /// Level to use at `distance`, given the level currently in use.
+    fn select(&self, distance: f32, current: Option<usize>) -> usize {
+        let target = self
+            .levels
+            .iter()
+            .position(|level| distance <= level.max_distance)
+            .unwrap_or(self.levels.len() - 1);
+        match current {
+            Some(current)
+                if target > current
+                    && distance <= self.levels[current].max_distance * (1.0 + self.hysteresis) =>
+            {
+                current
+            }
+            _ => target,
+        }
+    }
+
+    /// Level to use when the bounding sphere spans `pixels`, given the
+    /// level currently in use.
+    fn select_by_pixels(&self, pixels: f32, current: Option<usize>) -> usize {
+        let resolution = |level: usize| self.levels[level].resolution as f32;
+        let covering = (0..self.levels.len())
+            .filter(|&level| resolution(level) >= pixels)
+            .min_by(|&a, &b| resolution(a).total_cmp(&resolution(b)));
+        let target = covering.unwrap_or_else(|| {
+            (0..self.levels.len())
+                .max_by(|&a, &b| resolution(a).total_cmp(&resolution(b)))
+                .unwrap_or(0)
+        });
+        match current {
+            Some(current)
+                if resolution(target) < resolution(current)
+                    && pixels * (1.0 + self.hysteresis) > resolution(target) =>
+            {
+                current
+            }
+            _ => target,
+        }
+    }
+}
+
+/// Projected height, in pixels, of a sphere of radius 1 at `distance`
+/// from a camera with projection `clip_from_view` and a viewport
+/// `viewport_height` pixels tall.
+///
+/// The clip-space `w` is `distance` for a perspective projection and `1`
+/// for an orthographic one; reading it off the matrix covers both, and any
+/// [`Projection::Custom`] built the same way.
+fn pixels_per_unit_radius(clip_from_view: Mat4, viewport_height: f32, distance: f32) -> f32 {
+    let w = clip_from_view.w_axis.w - clip_from_view.z_axis.w * distance;
+    clip_from_view.y_axis.y * viewport_height / w.max(f32::EPSILON)
+}
+
+/// An active camera as seen by [`update_procedural_material_lods`].
+struct Eye {
+    position: Vec3,
+    /// Projection and viewport height, when both are known.
+    screen: Option<(Mat4, f32)>,
+}
+
+impl Eye {
+    /// Projected height in pixels of a sphere of `radius` at `position`.
+    fn pixels(&self, position: Vec3, radius: f32) -> Option<f32> {
+        let (clip_from_view, height) = self.screen?;
+        let distance = self.position.distance(position);
+        Some(radius * pixels_per_unit_radius(clip_from_view, height, distance))
+    }
+}
+
+/// Bevy system — picks each [`ProceduralMaterialLod`]'s level from its
+/// distance to the nearest active camera (or its largest projected size,
+/// per [`LodMetric`]) and, when the level (or the
+/// texture config) changed, requests the textures at that resolution:
+/// served synchronously from a memory [`TextureCache`] hit, otherwise
+/// dispatched as a [`PatchMaterialTextures`](crate::material::PatchMaterialTextures)
+/// task that supersedes (and cancels) any earlier one for the material.
+///
+/// Registered automatically for [`StandardMaterial`] by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin), and for other
+/// types by [`ProceduralMaterialPlugin`](crate::ProceduralMaterialPlugin).
+pub fn update_procedural_material_lods<M: TextureSlots>(
+    mut commands: Commands,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&Projection>)>,
+    mut lods: Query<(&GlobalTransform, &mut ProceduralMaterialLod<M>)>,
+    mut materials: ResMut<Assets<M>>,
+    mut images: ResMut<Assets<Image>>,
+    cache: Option<Res<TextureCache>>,
+) {
+    let eyes: Vec<_> = cameras
+        .iter()
+        .filter(|(camera, _, _)| camera.is_active)
+        .map(|(camera, transform, projection)| Eye {
+            position: transform.translation(),
+            screen: projection
+                .zip(camera.physical_viewport_size())
+                .map(|(projection, viewport)| (projection.get_clip_from_view(), viewport.y as f32)),
+        })
+        .collect();
+    if eyes.is_empty() {
+        return;
+    }
+
+    for (transform, mut lod) in &mut lods {
+        let position = transform.translation();
+        let current = lod.applied.map(|(level, _)| level);
+        let level = match lod.metric {
+            LodMetric::Distance => {
+                let distance = eyes
+                    .iter()
+                    .map(|eye| eye.position.distance(position))
+                    .fold(f32::INFINITY, f32::min);
+                lod.select(distance, current)
+            }
+            LodMetric::ScreenSize { radius } => {
+                let Some(pixels) = eyes
+                    .iter()
+                    .filter_map(|eye| eye.pixels(position, radius))
+                    .reduce(f32::max)
+                else {
+                    continue;
+                };
+                lod.select_by_pixels(pixels, current)
+            }
+        };
+        let fingerprint = lod.settings.texture.fingerprint();
+        if lod.applied == Some((level, fingerprint)) {
+            continue;
+        }
+
+        if let Some(previous) = lod.in_flight.take() {
+            commands.entity(previous).try_despawn();
+        }
+        let resolution = lod.levels[level].resolution;
+        lod.in_flight = request_material_textures(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            cache.as_deref(),
+            &lod.settings.texture,
+            &lod.material,
+            resolution,
+            resolution,
+            lod.settings.uses_parallax(),
+            lod.settings.preview_size,
+        );
+        lod.applied = Some((level, fingerprint));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ladder() -> ProceduralMaterialLod {
+        ProceduralMaterialLod::new(
+            MaterialSettings::default(),
+            Handle::default(),
+            [
+                LodLevel {
+                    max_distance: 50.0,
+                    resolution: 256,
+                },
+                LodLevel {
+                    max_distance: 10.0,
+                    resolution: 1024,
+                },
+                LodLevel {
+                    max_distance: f32::INFINITY,
+                    resolution: 64,
+                },
+            ],
+        )
+        .with_hysteresis(0.2)
+    }
+
+    /// Levels are sorted nearest first, and the first level whose range
+    /// covers the distance wins when nothing is applied yet.
+    #[test]
+    fn select_picks_the_nearest_covering_level() {
+        let lod = ladder();
+        let resolutions: Vec<u32> = lod.levels().iter().map(|l| l.resolution).collect();
+        assert_eq!(resolutions, [1024, 256, 64]);
+        assert_eq!(lod.select(5.0, None), 0);
+        assert_eq!(lod.select(10.0, None), 0);
+        assert_eq!(lod.select(30.0, None), 1);
+        assert_eq!(lod.select(1e6, None), 2);
+    }
+
+    /// Refining happens at the boundary; coarsening waits until the camera
+    /// is `hysteresis` past it.
+    #[test]
+    fn select_coarsens_only_past_the_hysteresis_band() {
+        let lod = ladder();
+        assert_eq!(lod.select(9.0, Some(1)), 0);
+        assert_eq!(lod.select(11.0, Some(0)), 0);
+        assert_eq!(lod.select(12.5, Some(0)), 1);
+        assert_eq!(lod.select(59.0, Some(1)), 1);
+        assert_eq!(lod.select(200.0, Some(0)), 2);
+    }
+
+    /// Under the screen-size metric the smallest covering resolution wins,
+    /// the finest level covers anything larger, and coarsening waits for
+    /// the hysteresis band below the coarser level's resolution.
+    #[test]
+    fn select_by_pixels_picks_the_smallest_covering_resolution() {
+        let lod = ladder();
+        // Levels sorted nearest first: 1024, 256, 64.
+        assert_eq!(lod.select_by_pixels(10.0, None), 2);
+        assert_eq!(lod.select_by_pixels(64.0, None), 2);
+        assert_eq!(lod.select_by_pixels(100.0, None), 1);
+        assert_eq!(lod.select_by_pixels(5000.0, None), 0);
+
+        assert_eq!(lod.select_by_pixels(300.0, Some(1)), 0);
+        assert_eq!(lod.select_by_pixels(240.0, Some(0)), 0);
+        assert_eq!(lod.select_by_pixels(200.0, Some(0)), 1);
+        assert_eq!(lod.select_by_pixels(10.0, Some(0)), 2);
+    }
+
+    /// A sphere's projected height follows the field of view and distance
+    /// for perspective cameras and the view height for orthographic ones.
+    #[test]
+    fn projected_size_follows_the_projection() {
+        use bevy::camera::{
+            CameraProjection, OrthographicProjection, PerspectiveProjection, ScalingMode,
+        };
+
+        let perspective = Projection::Perspective(PerspectiveProjection {
+            fov: std::f32::consts::FRAC_PI_2,
+            ..Default::default()
+        });
+        // A 90° vertical FOV spans 2 units at distance 1, so a unit radius
+        // at distance 10 covers a tenth of the viewport.
+        let eye = Eye {
+            position: Vec3::ZERO,
+            screen: Some((perspective.get_clip_from_view(), 1000.0)),
+        };
+        let pixels = eye.pixels(Vec3::new(0.0, 0.0, -10.0), 1.0).unwrap();
+        assert!((pixels - 100.0).abs() < 1e-2, "perspective {pixels}");
+        let farther = eye.pixels(Vec3::new(0.0, 0.0, -20.0), 1.0).unwrap();
+        assert!((farther - 50.0).abs() < 1e-2, "perspective {farther}");
+
+        let mut ortho = OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: 20.0,
+            },
+            ..OrthographicProjection::default_3d()
+        };
+        ortho.update(1000.0, 1000.0);
+        let eye = Eye {
+            position: Vec3::ZERO,
+            screen: Some((Projection::Orthographic(ortho).get_clip_from_view(), 1000.0)),
+        };
+        for distance in [5.0, 500.0] {
+            let pixels = eye.pixels(Vec3::new(0.0, 0.0, -distance), 1.0).unwrap();
+            assert!((pixels - 100.0).abs() < 1e-2, "orthographic {pixels}");
+        }
+
+        let unknown = Eye {
+            position: Vec3::ZERO,
+            screen: None,
+        };
+        assert_eq!(unknown.pixels(Vec3::ONE, 1.0), None);
+    }
+}
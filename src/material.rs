@@ -15,23 +15,44 @@
 //! Optional caching is provided by inserting a [`TextureCache`] resource
 //! before the helper runs.  Cache hits return the previous handles without
 //! re-running the generator.
+//!
+//! Setting [`MaterialSettings::parallax_depth_scale`] above zero also
+//! reconstructs a height field from the generated normal map (see
+//! [`HeightMap`](crate::height::HeightMap)) and binds it as the material's
+//! `depth_map`, giving bricks, cobblestone and pavers parallax relief
+//! without extra geometry.  The built-in generators do not return their
+//! own field, so the relief is an approximation — shallower on steep
+//! steps and normalised per material.
+//!
+//! Other material types — an `ExtendedMaterial` or an app's own
+//! `Material` — receive generated maps by implementing [`TextureSlots`],
+//! which names the slot each map goes to.  Register
+//! [`ProceduralMaterialPlugin`](crate::ProceduralMaterialPlugin) for the
+//! type and request textures with [`request_procedural_textures`].
 
+use std::borrow::Cow;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use bevy::asset::Assets;
+use bevy::asset::{Asset, Assets, RenderAssetUsages};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::Entity;
+use bevy::ecs::event::EntityEvent;
 use bevy::ecs::system::{Commands, Query, ResMut};
 use bevy::image::Image;
 use bevy::math::{Affine2, Vec2};
-use bevy::pbr::StandardMaterial;
+use bevy::pbr::{
+    ExtendedMaterial, Material, MaterialExtension, ParallaxMappingMethod, StandardMaterial,
+};
 use bevy::prelude::{AlphaMode, Color, Handle, LinearRgba};
-use bevy::render::render_resource::Face;
+use bevy::reflect::TypePath;
+use bevy::render::render_resource::{Extent3d, Face, TextureDimension, TextureFormat};
 
-use crate::async_gen::PendingTexture;
-use crate::cache::{TextureCache, TextureCacheKey};
-use crate::generator::{map_to_images, map_to_images_card};
+use crate::async_gen::{
+    PendingTexture, TextureFailure, TexturePriority, UploadBudget, by_priority, take_admission,
+};
+use crate::cache::{PixelLoader, TextureCache, TextureCacheKey};
+use crate::generator::GeneratedHandles;
 
 /// PBR rendering hints derived from a [`TextureConfig`] variant.
 ///
@@ -50,7 +71,8 @@ pub struct RenderProperties {
     /// `None` for cards (no culling, so both sides render).
     pub cull_mode: Option<Face>,
     /// `true` when generated images should be uploaded with
-    /// [`map_to_images_card`] (clamp-to-edge); `false` for tiling surfaces.
+    /// [`map_to_images_card`](crate::generator::map_to_images_card)
+    /// (clamp-to-edge); `false` for tiling surfaces.
     pub is_card: bool,
 }
 
@@ -134,6 +156,49 @@ macro_rules! define_texture_config {
                 }
             }
 
+            /// [`spawn`](TextureConfig::spawn), additionally recovering the
+            /// generator's height field on the worker (see
+            /// [`HeightMap`](crate::height::HeightMap)).  The polling
+            /// systems upload it as [`GeneratedHandles::height`].
+            pub fn spawn_with_height(&self, width: u32, height: u32) -> Option<PendingTexture> {
+                let is_card = self.render_properties().is_card;
+                match self {
+                    Self::None => None,
+                    $(Self::$variant(c) => Some(PendingTexture::from_generator(
+                        <$generator_ty>::new(c.clone()),
+                        width,
+                        height,
+                        is_card,
+                        true,
+                    ))),*,
+                }
+            }
+
+            /// Submit a task that serves this config from `loader` (a cache
+            /// backend's deferred disk read) and generates only when the
+            /// read yields nothing.  With `with_height`, the height field is
+            /// recovered on the worker either way.
+            pub(crate) fn spawn_cached(
+                &self,
+                loader: PixelLoader,
+                width: u32,
+                height: u32,
+                with_height: bool,
+            ) -> Option<PendingTexture> {
+                let is_card = self.render_properties().is_card;
+                match self {
+                    Self::None => None,
+                    $(Self::$variant(c) => Some(PendingTexture::from_cache_or_generator(
+                        loader,
+                        <$generator_ty>::new(c.clone()),
+                        width,
+                        height,
+                        is_card,
+                        with_height,
+                    ))),*,
+                }
+            }
+
             /// One default-config instance of every generator variant, in
             /// registry order.
             ///
@@ -258,12 +323,45 @@ macro_rules! impl_texture_config_genotype {
 
 symbios_texture::for_each_generator!(impl_texture_config_genotype);
 
+/// How the shader marches the height map when parallax mapping is enabled
+/// (see [`MaterialSettings::parallax_depth_scale`]).
+///
+/// Serialisable mirror of Bevy's [`ParallaxMappingMethod`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ParallaxMethod {
+    /// Parallax occlusion mapping: a linear search through the depth layers
+    /// with one interpolation step — cheap and good enough for most
+    /// surfaces.
+    #[default]
+    Occlusion,
+    /// Relief mapping: the occlusion search refined by up to `max_steps`
+    /// binary-search steps.  Sharper silhouettes on deep relief (cobbles,
+    /// pavers) at extra per-pixel cost.
+    Relief {
+        /// Maximum number of refinement steps.
+        max_steps: u32,
+    },
+}
+
+impl From<ParallaxMethod> for ParallaxMappingMethod {
+    fn from(method: ParallaxMethod) -> Self {
+        match method {
+            ParallaxMethod::Occlusion => ParallaxMappingMethod::Occlusion,
+            ParallaxMethod::Relief { max_steps } => ParallaxMappingMethod::Relief { max_steps },
+        }
+    }
+}
+
 /// PBR material settings driven by a [`TextureConfig`].
 ///
 /// All numeric fields are plain `f32`/`[f32; 3]` — applications that need
 /// DAG-CBOR / fixed-point serialisation (e.g. blockchain payloads) should
 /// keep their own mirror type and convert at the boundary.
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+///
+/// Also a Bevy [`Asset`]: `.symtex` files load as
+/// `Handle<MaterialSettings>` for hot-reloadable materials (see
+/// [`ProceduralMaterialSource`](crate::loader::ProceduralMaterialSource)).
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Asset, TypePath)]
 pub struct MaterialSettings {
     /// Base colour (linear RGB).
     pub base_color: [f32; 3],
@@ -280,6 +378,13 @@ pub struct MaterialSettings {
     /// `emission_color` / `emission_strength` here only to tint or brighten
     /// (e.g. above 1.0 for HDR bloom).
     pub emission_strength: f32,
+    /// Hold the emission factor back until the generated glow map is
+    /// bound: the untextured material carries a black placeholder emissive
+    /// texture, so a factor set here lights up only where the map glows
+    /// instead of the whole surface while the textures generate.  Ignored
+    /// for [`TextureConfig::None`].  Default `false`.
+    #[serde(default)]
+    pub emission_needs_map: bool,
     /// Perceptual roughness in `[0, 1]`.
     pub roughness: f32,
     /// Metallic factor in `[0, 1]`.
@@ -287,10 +392,86 @@ pub struct MaterialSettings {
     /// UV repeat scale.  `1.0` means one tile across the mesh; `2.0` means
     /// the texture repeats twice along U and V.
     pub uv_scale: f32,
+    /// UV translation applied after scale and rotation, in tiles.
+    #[serde(default)]
+    pub uv_offset: [f32; 2],
+    /// Counter-clockwise UV rotation in radians, applied after scale.
+    #[serde(default)]
+    pub uv_rotation: f32,
+    /// `StandardMaterial::reflectance` — specular intensity of
+    /// non-metals, in `[0, 1]`.  `0.5` (the default) is 4% reflectance,
+    /// right for most dielectrics; ice and glass sit slightly lower.
+    #[serde(default = "default_reflectance")]
+    pub reflectance: f32,
+    /// `StandardMaterial::clearcoat` — strength of a thin glossy layer on
+    /// top (lacquer, car paint, wet stone), in `[0, 1]`.  Default `0.0`.
+    #[serde(default)]
+    pub clearcoat: f32,
+    /// `StandardMaterial::clearcoat_perceptual_roughness` — roughness of
+    /// the clearcoat layer, in `[0, 1]`.  Default `0.5`.
+    #[serde(default = "default_clearcoat_roughness")]
+    pub clearcoat_roughness: f32,
+    /// `StandardMaterial::anisotropy_strength` — how far highlights
+    /// stretch along the tangent (brushed metal), in `[0, 1]`.  Needs mesh
+    /// tangents.  Default `0.0`.
+    #[serde(default)]
+    pub anisotropy_strength: f32,
+    /// `StandardMaterial::anisotropy_rotation` — direction of the stretch
+    /// in radians, counter-clockwise from the tangent.
+    #[serde(default)]
+    pub anisotropy_rotation: f32,
+    /// `StandardMaterial::specular_transmission` — share of light passing
+    /// straight through (glass, ice), in `[0, 1]`.  Default `0.0`.
+    #[serde(default)]
+    pub specular_transmission: f32,
+    /// `StandardMaterial::thickness` — volume thickness (world units) used
+    /// to refract transmitted light.  `0.0` (the default) treats the
+    /// surface as a thin sheet.
+    #[serde(default)]
+    pub thickness: f32,
+    /// `StandardMaterial::ior` — index of refraction for transmitted
+    /// light.  Default `1.5` (glass); ice is about `1.31`.
+    #[serde(default = "default_ior")]
+    pub ior: f32,
     /// Procedural texture configuration; [`TextureConfig::None`] leaves the
     /// material's texture slots untouched.
     #[serde(default)]
     pub texture: TextureConfig,
+    /// Take the PBR factors — colour, emission, roughness, metallic,
+    /// reflectance, clearcoat, anisotropy, transmission — from
+    /// [`recommended_for`](MaterialSettings::recommended_for) for
+    /// [`texture`](MaterialSettings::texture) instead of these fields.
+    /// UV, parallax and preview fields still apply.  Lets
+    /// [`build_procedural_material_async`] and `.symtex` files
+    /// (`use_recommended: true`) pick up the presets without spelling
+    /// them out.  Default `false`.
+    #[serde(default)]
+    pub use_recommended: bool,
+    /// `StandardMaterial::parallax_depth_scale` — relief depth relative to
+    /// the UV tile size.  `0.0` (the default) disables parallax mapping;
+    /// any positive value also reconstructs a height map from the normals
+    /// and binds it as the material's `depth_map`.  Each map spans the
+    /// full `[0, 1]` range, so tune the scale per material.  Around
+    /// `0.02`–`0.1` looks right for most masonry; larger values break up
+    /// at grazing angles.
+    #[serde(default)]
+    pub parallax_depth_scale: f32,
+    /// `StandardMaterial::parallax_mapping_method`; only consulted when
+    /// [`parallax_depth_scale`](MaterialSettings::parallax_depth_scale) is
+    /// positive.
+    #[serde(default)]
+    pub parallax_mapping_method: ParallaxMethod,
+    /// Longer side, in texels, of a low-resolution preview generated ahead
+    /// of the full texture and patched in as soon as it is ready, so a
+    /// large material does not sit flat-coloured for the whole bake.
+    /// `0` (the default) skips the preview; sizes not below the full
+    /// resolution are ignored.  `64` is plenty for a first impression.
+    ///
+    /// Honoured by [`build_procedural_material_async`] and
+    /// [`ProceduralMaterialSource`](crate::loader::ProceduralMaterialSource);
+    /// the `.symtex` asset loader only delivers finished images.
+    #[serde(default)]
+    pub preview_size: u32,
 }
 
 impl Default for MaterialSettings {
@@ -299,32 +480,388 @@ impl Default for MaterialSettings {
             base_color: [0.6, 0.4, 0.2],
             emission_color: [0.0, 0.0, 0.0],
             emission_strength: 0.0,
+            emission_needs_map: false,
             roughness: 0.5,
             metallic: 0.0,
             uv_scale: 1.0,
+            uv_offset: [0.0, 0.0],
+            uv_rotation: 0.0,
+            reflectance: default_reflectance(),
+            clearcoat: 0.0,
+            clearcoat_roughness: default_clearcoat_roughness(),
+            anisotropy_strength: 0.0,
+            anisotropy_rotation: 0.0,
+            specular_transmission: 0.0,
+            thickness: 0.0,
+            ior: default_ior(),
             texture: TextureConfig::None,
+            use_recommended: false,
+            parallax_depth_scale: 0.0,
+            parallax_mapping_method: ParallaxMethod::Occlusion,
+            preview_size: 0,
         }
     }
 }
 
+/// `StandardMaterial`'s default reflectance, for files that omit it.
+fn default_reflectance() -> f32 {
+    0.5
+}
+
+/// `StandardMaterial`'s default clearcoat roughness, for files that omit
+/// it.
+fn default_clearcoat_roughness() -> f32 {
+    0.5
+}
+
+/// `StandardMaterial`'s default index of refraction, for files that omit
+/// it.
+fn default_ior() -> f32 {
+    1.5
+}
+
+impl MaterialSettings {
+    /// Settings tuned to show `texture` as its generator intends.
+    ///
+    /// The albedo and ORM maps already carry each generator's colour,
+    /// roughness and metallic response, so the base factors pass them
+    /// through: white `base_color`, `roughness` `1.0`.  On top of that, the
+    /// generators whose look the maps cannot express get their
+    /// `StandardMaterial` factors:
+    ///
+    /// | Generators | Adjustments |
+    /// |---|---|
+    /// | metal, corrugated, iron_grille, chain_link | `metallic` `1.0`, so the ORM metallic channel applies |
+    /// | ice | `specular_transmission` `0.6`, `thickness` `0.2`, `ior` `1.31`, matching `reflectance` |
+    /// | stained_glass | `specular_transmission` `0.5` through the panes, `ior` `1.5` |
+    /// | marble | polished `clearcoat` `0.6`, `clearcoat_roughness` `0.1` |
+    /// | lava | white `emission_color` at `emission_strength` `2.0`, with [`emission_needs_map`](MaterialSettings::emission_needs_map) |
+    ///
+    /// Lava's factor brightens its glow map for bloom; gating it on the map
+    /// keeps the untextured material from glowing uniformly while the
+    /// textures generate.
+    ///
+    /// Generators without an entry — including any added to the registry
+    /// later — get the pass-through factors alone.  Everything else is
+    /// [`MaterialSettings::default`]; adjust the returned value freely, or
+    /// set [`use_recommended`](MaterialSettings::use_recommended) to apply
+    /// the presets wherever settings turn into a material:
+    ///
+    /// ```rust,ignore
+    /// let settings = MaterialSettings {
+    ///     uv_scale: 4.0,
+    ///     ..MaterialSettings::recommended_for(&TextureConfig::Metal(MetalConfig::default()))
+    /// };
+    /// ```
+    pub fn recommended_for(texture: &TextureConfig) -> Self {
+        use TextureConfig as T;
+
+        let mut settings = Self {
+            base_color: [1.0, 1.0, 1.0],
+            roughness: 1.0,
+            texture: texture.clone(),
+            ..Self::default()
+        };
+        match texture {
+            T::Metal(_) | T::Corrugated(_) | T::IronGrille(_) | T::ChainLink(_) => {
+                settings.metallic = 1.0;
+            }
+            T::Ice(_) => {
+                settings.specular_transmission = 0.6;
+                settings.thickness = 0.2;
+                settings.ior = 1.31;
+                // F0 of ice (about 1.8%) on `reflectance`'s 0.16·r² scale.
+                settings.reflectance = 0.34;
+            }
+            T::StainedGlass(_) => {
+                settings.specular_transmission = 0.5;
+                settings.ior = 1.5;
+            }
+            T::Marble(_) => {
+                settings.clearcoat = 0.6;
+                settings.clearcoat_roughness = 0.1;
+            }
+            T::Lava(_) => {
+                settings.emission_color = [1.0, 1.0, 1.0];
+                settings.emission_strength = 2.0;
+                settings.emission_needs_map = true;
+            }
+            _ => {}
+        }
+        settings
+    }
+
+    /// These settings with [`use_recommended`](MaterialSettings::use_recommended)
+    /// applied: the preset's PBR factors around this value's texture, UV,
+    /// parallax and preview fields.
+    fn resolved(&self) -> Cow<'_, Self> {
+        if !self.use_recommended {
+            return Cow::Borrowed(self);
+        }
+        Cow::Owned(Self {
+            uv_scale: self.uv_scale,
+            uv_offset: self.uv_offset,
+            uv_rotation: self.uv_rotation,
+            use_recommended: true,
+            parallax_depth_scale: self.parallax_depth_scale,
+            parallax_mapping_method: self.parallax_mapping_method,
+            preview_size: self.preview_size,
+            ..Self::recommended_for(&self.texture)
+        })
+    }
+
+    /// `true` when these settings ask for a generated height map: a
+    /// positive [`parallax_depth_scale`](MaterialSettings::parallax_depth_scale)
+    /// on a textured material.
+    pub fn uses_parallax(&self) -> bool {
+        self.parallax_depth_scale > 0.0 && !matches!(self.texture, TextureConfig::None)
+    }
+
+    /// `StandardMaterial::uv_transform` for [`uv_scale`](Self::uv_scale),
+    /// [`uv_rotation`](Self::uv_rotation) and
+    /// [`uv_offset`](Self::uv_offset), applied in that order.
+    pub fn uv_transform(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(
+            Vec2::splat(self.uv_scale),
+            self.uv_rotation,
+            Vec2::from_array(self.uv_offset),
+        )
+    }
+
+    /// The untextured [`StandardMaterial`] these settings describe: PBR
+    /// factors (including clearcoat, anisotropy and transmission), UV
+    /// transform, parallax parameters, and the alpha / culling flags from
+    /// [`TextureConfig::render_properties`].
+    ///
+    /// Texture slots are left empty — [`build_procedural_material_async`]
+    /// and the `.symtex` asset loader fill them once the generated (or
+    /// cached) images are available — except the emissive slot of a
+    /// material whose [`emission_needs_map`](MaterialSettings::emission_needs_map)
+    /// holds, which gets a black placeholder until its glow map arrives.
+    pub fn to_standard_material(&self) -> StandardMaterial {
+        let settings = self.resolved();
+        let props = settings.texture.render_properties();
+        let emissive = Color::srgb_from_array(settings.emission_color).to_linear()
+            * settings.emission_strength;
+        let gate_emission =
+            settings.emission_needs_map && !matches!(settings.texture, TextureConfig::None);
+
+        StandardMaterial {
+            base_color: Color::srgb_from_array(settings.base_color),
+            perceptual_roughness: settings.roughness,
+            metallic: settings.metallic,
+            emissive,
+            emissive_texture: gate_emission.then_some(EMISSIVE_PLACEHOLDER),
+            alpha_mode: props.alpha_mode,
+            double_sided: props.double_sided,
+            cull_mode: props.cull_mode,
+            reflectance: settings.reflectance,
+            clearcoat: settings.clearcoat,
+            clearcoat_perceptual_roughness: settings.clearcoat_roughness,
+            anisotropy_strength: settings.anisotropy_strength,
+            anisotropy_rotation: settings.anisotropy_rotation,
+            specular_transmission: settings.specular_transmission,
+            thickness: settings.thickness,
+            ior: settings.ior,
+            uv_transform: settings.uv_transform(),
+            parallax_depth_scale: settings.parallax_depth_scale,
+            parallax_mapping_method: settings.parallax_mapping_method.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Black 1×1 emissive texture standing in for a glow map that has not
+/// arrived yet, so the emission factor multiplies nothing.  Bound by
+/// [`MaterialSettings::to_standard_material`] and
+/// [`apply_emissive_map`]; registered by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin).
+pub(crate) const EMISSIVE_PLACEHOLDER: Handle<Image> =
+    bevy::asset::uuid_handle!("a9186f43-59d5-4d5a-a564-ca7c8e4635ae");
+
+/// Add the [`EMISSIVE_PLACEHOLDER`] image to `images`.
+pub(crate) fn register_emissive_placeholder(images: &mut Assets<Image>) {
+    let image = Image::new_fill(
+        Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    images
+        .insert(&EMISSIVE_PLACEHOLDER, image)
+        .expect("UUID handles are always valid");
+}
+
+/// A material type generated maps can be written into: each setter names
+/// the slot receiving one map.
+///
+/// Implemented for [`StandardMaterial`] and for any `ExtendedMaterial`
+/// over an implementing base (the maps go to the base).  A custom
+/// material implements the slots it has; maps it has no slot for are
+/// dropped:
+///
+/// ```rust,ignore
+/// impl TextureSlots for ToonMaterial {
+///     fn set_albedo(&mut self, albedo: Handle<Image>) {
+///         self.color_texture = Some(albedo);
+///     }
+///     fn has_albedo(&self) -> bool {
+///         self.color_texture.is_some()
+///     }
+///     fn set_normal(&mut self, normal: Handle<Image>) {
+///         self.normal_texture = Some(normal);
+///     }
+/// }
+/// ```
+pub trait TextureSlots: Asset {
+    /// Bind the sRGB albedo map.
+    fn set_albedo(&mut self, albedo: Handle<Image>);
+    /// `true` while an albedo map is bound; a material without one gets
+    /// the [`MaterialSettings::preview_size`] preview.
+    fn has_albedo(&self) -> bool;
+    /// Bind the tangent-space normal map.
+    fn set_normal(&mut self, _normal: Handle<Image>) {}
+    /// Bind the ORM map (occlusion R, roughness G, metallic B).
+    fn set_orm(&mut self, _orm: Handle<Image>) {}
+    /// Bind the emissive map, or clear it for a generator without one.
+    fn set_emissive(&mut self, _emissive: Option<Handle<Image>>) {}
+    /// Bind the height map, or clear it when none was requested.
+    fn set_height(&mut self, _height: Option<Handle<Image>>) {}
+}
+
+impl TextureSlots for StandardMaterial {
+    fn set_albedo(&mut self, albedo: Handle<Image>) {
+        self.base_color_texture = Some(albedo);
+    }
+
+    fn has_albedo(&self) -> bool {
+        self.base_color_texture.is_some()
+    }
+
+    fn set_normal(&mut self, normal: Handle<Image>) {
+        self.normal_map_texture = Some(normal);
+    }
+
+    fn set_orm(&mut self, orm: Handle<Image>) {
+        self.metallic_roughness_texture = Some(orm);
+    }
+
+    /// Defaults the emissive factor to white when a glow map is present
+    /// (and undoes it when one is not), so the map is visible without the
+    /// caller configuring emission.  A tinted or brightened factor is
+    /// left alone.
+    fn set_emissive(&mut self, emissive: Option<Handle<Image>>) {
+        apply_emissive_map(self, emissive);
+    }
+
+    fn set_height(&mut self, height: Option<Handle<Image>>) {
+        self.depth_map = height;
+    }
+}
+
+impl<B, E> TextureSlots for ExtendedMaterial<B, E>
+where
+    B: Material + TextureSlots,
+    E: MaterialExtension,
+{
+    fn set_albedo(&mut self, albedo: Handle<Image>) {
+        self.base.set_albedo(albedo);
+    }
+
+    fn has_albedo(&self) -> bool {
+        self.base.has_albedo()
+    }
+
+    fn set_normal(&mut self, normal: Handle<Image>) {
+        self.base.set_normal(normal);
+    }
+
+    fn set_orm(&mut self, orm: Handle<Image>) {
+        self.base.set_orm(orm);
+    }
+
+    fn set_emissive(&mut self, emissive: Option<Handle<Image>>) {
+        self.base.set_emissive(emissive);
+    }
+
+    fn set_height(&mut self, height: Option<Handle<Image>>) {
+        self.base.set_height(height);
+    }
+}
+
 /// Marker for an in-flight procedural-texture task whose result should be
-/// patched directly onto a [`StandardMaterial`].
+/// patched directly onto a material — a [`StandardMaterial`] unless `M`
+/// says otherwise.
 ///
 /// Spawned alongside a [`PendingTexture`] by
-/// [`build_procedural_material_async`].  Consumed by
-/// [`patch_procedural_material_textures`].  Removing or despawning the entity
-/// before completion cancels the task (via `PendingTexture`'s drop flag) and
-/// leaves the material untouched.
+/// [`build_procedural_material_async`] and [`request_procedural_textures`].
+/// Consumed by [`patch_procedural_material_textures`].  Removing or
+/// despawning the entity before completion cancels the task (via
+/// `PendingTexture`'s drop flag) and leaves the material untouched.
 #[derive(Component)]
-pub struct PatchMaterialTextures {
-    /// Material whose `base_color_texture` / `normal_map_texture` /
-    /// `metallic_roughness_texture` slots receive the generated images.
-    pub target: Handle<StandardMaterial>,
+#[require(PatchesMaterial)]
+pub struct PatchMaterialTextures<M: TextureSlots = StandardMaterial> {
+    /// Material whose [`TextureSlots`] receive the generated images.
+    pub target: Handle<M>,
     /// Cache key the result should be stored under, when a [`TextureCache`]
     /// is present.  `None` disables caching for this task.
     pub cache_key: Option<TextureCacheKey>,
 }
 
+/// Added with every [`PatchMaterialTextures`], whatever its material type,
+/// so [`poll_texture_tasks`](crate::async_gen::poll_texture_tasks) can
+/// leave those tasks to the patch systems.
+#[derive(Component, Default)]
+pub struct PatchesMaterial;
+
+/// Triggered by [`patch_procedural_material_textures`] once a
+/// [`PatchMaterialTextures`] task's images are written into its material,
+/// just before the task entity is despawned:
+///
+/// ```rust,ignore
+/// app.add_observer(|patched: On<MaterialTexturesPatched>| {
+///     info!("{:?} is textured", patched.target);
+/// });
+/// ```
+#[derive(EntityEvent, Debug)]
+pub struct MaterialTexturesPatched<M: TextureSlots = StandardMaterial> {
+    /// The task entity, despawned once observers have run.
+    pub entity: Entity,
+    /// [`PatchMaterialTextures::target`] of the finished task.
+    pub target: Handle<M>,
+    /// `true` for a [`MaterialSettings::preview_size`] preview; the
+    /// full-resolution textures follow in a later trigger.
+    pub preview: bool,
+}
+
+/// Triggered by [`patch_procedural_material_textures`] when a
+/// [`PatchMaterialTextures`] task fails, just before its entity is
+/// despawned.  The material keeps whatever textures it had; observe this
+/// to assign a placeholder or retry at a smaller size:
+///
+/// ```rust,ignore
+/// app.add_observer(|failed: On<MaterialTexturesFailed>| {
+///     warn!("{:?} stays untextured: {}", failed.target, failed.failure);
+/// });
+/// ```
+#[derive(EntityEvent, Debug)]
+pub struct MaterialTexturesFailed<M: TextureSlots = StandardMaterial> {
+    /// The task entity, despawned once observers have run.
+    pub entity: Entity,
+    /// [`PatchMaterialTextures::target`] of the failed task.
+    pub target: Handle<M>,
+    /// [`PatchMaterialTextures::cache_key`] of the failed task; carries
+    /// the generator kind and requested size when caching was enabled.
+    pub cache_key: Option<TextureCacheKey>,
+    /// What went wrong.
+    pub failure: TextureFailure,
+}
+
 /// Assign a generator-produced emissive map to `material`, defaulting the
 /// emissive *factor* so the glow is actually visible.
 ///
@@ -342,8 +879,10 @@ pub struct PatchMaterialTextures {
 /// A caller-supplied non-black, non-white factor (e.g. a tinted or
 /// brightened glow set via [`MaterialSettings::emission_color`] /
 /// [`emission_strength`](MaterialSettings::emission_strength)) is left
-/// untouched in both directions.
-fn apply_emissive_map(material: &mut StandardMaterial, emissive: Option<Handle<Image>>) {
+/// untouched in both directions.  If such a factor was modulating a map and
+/// the map goes away, the black [`EMISSIVE_PLACEHOLDER`] takes its place,
+/// so the factor keeps lighting nothing rather than the whole surface.
+pub(crate) fn apply_emissive_map(material: &mut StandardMaterial, emissive: Option<Handle<Image>>) {
     // Compare RGB only: the emissive factor's alpha is not used for emission,
     // and `emission_color × emission_strength` yields `{0,0,0,0}` (alpha 0) at
     // the defaults — distinct from `LinearRgba::BLACK` (alpha 1).  White is
@@ -356,7 +895,193 @@ fn apply_emissive_map(material: &mut StandardMaterial, emissive: Option<Handle<I
         None if factor_is_auto_white => material.emissive = LinearRgba::BLACK,
         _ => {}
     }
-    material.emissive_texture = emissive;
+    let factor_is_custom = !factor_is_unset && !factor_is_auto_white;
+    material.emissive_texture = match emissive {
+        None if factor_is_custom && material.emissive_texture.is_some() => {
+            Some(EMISSIVE_PLACEHOLDER)
+        }
+        emissive => emissive,
+    };
+}
+
+/// Write uploaded texture handles into `material`'s [`TextureSlots`].
+pub(crate) fn apply_generated_handles<M: TextureSlots>(
+    material: &mut M,
+    handles: GeneratedHandles,
+) {
+    material.set_albedo(handles.albedo);
+    material.set_normal(handles.normal);
+    material.set_orm(handles.roughness);
+    material.set_height(handles.height);
+    material.set_emissive(handles.emissive);
+}
+
+/// Texture half of [`build_procedural_material_async`], shared with the
+/// hot-reload path: consult `cache`, and on a hit write the handles into
+/// `target` synchronously; on a miss dispatch `config` in the background
+/// with a [`PatchMaterialTextures`] tag aimed at `target`.
+///
+/// Backends with a [`PixelLoader`] ([`FileStore`]) are not read here: the
+/// dispatched task reads the blob on the pool and only generates if the
+/// read misses, so a cold disk cache never stalls the caller's system.
+///
+/// With `with_height`, a memory hit that carries no height map (cached by a
+/// non-parallax material) is applied at once but still regenerates, so the
+/// depth map follows a few frames later and the refreshed entry replaces the
+/// cached one.  Disk hits recover the height field on the worker.
+///
+/// When a task is dispatched for a still-untextured `target` and
+/// `preview_size` is set (see [`MaterialSettings::preview_size`]), a
+/// preview at that size goes through the same cache lookup and, if it
+/// misses too, runs ahead of the full task at
+/// [`Visible`](TexturePriority::Visible) priority.  Each level is cached
+/// under its own resolution.
+///
+/// Returns the spawned full-resolution task entity, or `None` when nothing
+/// was dispatched (full cache hit, or [`TextureConfig::None`]).  Despawning
+/// it also drops the preview.
+///
+/// [`FileStore`]: crate::cache::FileStore
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn request_material_textures<M: TextureSlots>(
+    commands: &mut Commands,
+    materials: &mut Assets<M>,
+    images: &mut Assets<Image>,
+    cache: Option<&TextureCache>,
+    config: &TextureConfig,
+    target: &Handle<M>,
+    width: u32,
+    height: u32,
+    with_height: bool,
+    preview_size: u32,
+) -> Option<Entity> {
+    let full = request_level(
+        commands,
+        materials,
+        images,
+        cache,
+        config,
+        target,
+        (width, height),
+        with_height,
+        TexturePriority::Normal,
+    )?;
+    let untextured = materials
+        .get(target)
+        .is_some_and(|material| !material.has_albedo());
+    if untextured && let Some(size) = preview_dimensions(width, height, preview_size) {
+        let preview = request_level(
+            commands,
+            materials,
+            images,
+            cache,
+            config,
+            target,
+            size,
+            with_height,
+            TexturePriority::Visible,
+        );
+        if let Some(preview) = preview {
+            commands.entity(preview).insert(PreviewOf(full));
+        }
+    }
+    Some(full)
+}
+
+/// `width × height` shrunk so its longer side is `size`, or `None` when
+/// that would not be smaller (including `size == 0`).
+fn preview_dimensions(width: u32, height: u32, size: u32) -> Option<(u32, u32)> {
+    let longest = width.max(height);
+    if size == 0 || size >= longest {
+        return None;
+    }
+    let scale = |side: u32| (u64::from(side) * u64::from(size) / u64::from(longest)).max(1) as u32;
+    Some((scale(width), scale(height)))
+}
+
+/// One resolution of [`request_material_textures`]: cache lookup, then
+/// dispatch at `priority` on a miss.
+#[allow(clippy::too_many_arguments)]
+fn request_level<M: TextureSlots>(
+    commands: &mut Commands,
+    materials: &mut Assets<M>,
+    images: &mut Assets<Image>,
+    cache: Option<&TextureCache>,
+    config: &TextureConfig,
+    target: &Handle<M>,
+    (width, height): (u32, u32),
+    with_height: bool,
+    priority: TexturePriority,
+) -> Option<Entity> {
+    if matches!(config, TextureConfig::None) {
+        return None;
+    }
+    let key = TextureCacheKey {
+        kind: config.label(),
+        fingerprint: config.fingerprint(),
+        width,
+        height,
+    };
+
+    // Disk-backed stores read their blob inside the task instead.
+    if let Some(loader) = cache.and_then(|c| c.pixel_loader(&key)) {
+        let pending = config.spawn_cached(loader, width, height, with_height)?;
+        return Some(spawn_patch_task(
+            commands,
+            pending.with_priority(priority),
+            target,
+            key,
+        ));
+    }
+
+    if let Some(handles) = cache.and_then(|c| c.get(&key, images)) {
+        let complete = !with_height || handles.height.is_some();
+        if let Some(material) = materials.get_mut(target) {
+            apply_generated_handles(material, (*handles).clone());
+        }
+        if complete {
+            return None;
+        }
+    }
+
+    let pending = if with_height {
+        config.spawn_with_height(width, height)?
+    } else {
+        config.spawn(width, height)?
+    };
+    Some(spawn_patch_task(
+        commands,
+        pending.with_priority(priority),
+        target,
+        key,
+    ))
+}
+
+/// Tags a preview task with the full-resolution task it stands in for.
+/// The preview is discarded once that task is gone — finished first, or
+/// cancelled — so it never overwrites the real textures.
+///
+/// Inserted by the material builders when
+/// [`MaterialSettings::preview_size`] is set.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PreviewOf(pub Entity);
+
+/// Spawn `pending` tagged to patch `target` and cache under `key`.
+fn spawn_patch_task<M: TextureSlots>(
+    commands: &mut Commands,
+    pending: PendingTexture,
+    target: &Handle<M>,
+    key: TextureCacheKey,
+) -> Entity {
+    commands
+        .spawn((
+            pending,
+            PatchMaterialTextures {
+                target: target.clone(),
+                cache_key: Some(key),
+            },
+        ))
+        .id()
 }
 
 /// One-shot helper: build a [`StandardMaterial`] from `settings`, dispatch
@@ -369,10 +1094,24 @@ fn apply_emissive_map(material: &mut StandardMaterial, emissive: Option<Handle<I
 /// `metallic` until the textures arrive a few frames later).
 ///
 /// If a [`TextureCache`] resource is provided, the cache is consulted
-/// before dispatching.  On cache hit the texture handles are written
+/// before dispatching.  On a memory hit the texture handles are written
 /// synchronously and no background task is spawned.  Disk-backed stores
-/// ([`FileStore`](crate::cache::FileStore)) upload their persisted pixels
-/// into `images` during that lookup, so hits survive process restarts.
+/// ([`FileStore`](crate::cache::FileStore)) are read by the background task
+/// instead — the blob is decoded on the pool and uploaded by
+/// [`patch_procedural_material_textures`], exactly like a generated map, and
+/// the generator only runs if the read misses.
+///
+/// When [`MaterialSettings::uses_parallax`] holds, the task also recovers
+/// the generator's height field and the patch binds it as `depth_map`;
+/// until then the material renders without relief.
+///
+/// With [`MaterialSettings::preview_size`] set, a low-resolution version
+/// is patched in first — typically within a frame or two — and replaced
+/// when the full bake lands.
+///
+/// Start from [`MaterialSettings::recommended_for`] to get the
+/// generator's intended roughness, metallic, transmission and clearcoat
+/// without tuning them by hand.
 ///
 /// Returns `Handle<StandardMaterial>`.
 pub fn build_procedural_material_async(
@@ -384,106 +1123,116 @@ pub fn build_procedural_material_async(
     width: u32,
     height: u32,
 ) -> Handle<StandardMaterial> {
-    let props = settings.texture.render_properties();
-    let emissive =
-        Color::srgb_from_array(settings.emission_color).to_linear() * settings.emission_strength;
-
-    let mut material = StandardMaterial {
-        base_color: Color::srgb_from_array(settings.base_color),
-        perceptual_roughness: settings.roughness,
-        metallic: settings.metallic,
-        emissive,
-        alpha_mode: props.alpha_mode,
-        double_sided: props.double_sided,
-        cull_mode: props.cull_mode,
-        uv_transform: Affine2::from_scale(Vec2::splat(settings.uv_scale)),
-        ..Default::default()
-    };
-
-    let cache_key = if matches!(settings.texture, TextureConfig::None) {
-        None
-    } else {
-        Some(TextureCacheKey {
-            kind: settings.texture.label(),
-            fingerprint: settings.texture.fingerprint(),
-            width,
-            height,
-        })
-    };
-
-    // Cache hit: write handles into the material before we hand it to Bevy.
-    // Full lookup — disk-backed stores read their blob and upload it into
-    // `images` here, so a FileStore hit short-circuits generation exactly
-    // like a memory hit.
-    if let (Some(key), Some(cache_ref)) = (cache_key.as_ref(), cache.as_deref())
-        && let Some(handles) = cache_ref.get(key, images)
-    {
-        material.base_color_texture = Some(handles.albedo.clone());
-        material.normal_map_texture = Some(handles.normal.clone());
-        material.metallic_roughness_texture = Some(handles.roughness.clone());
-        apply_emissive_map(&mut material, handles.emissive.clone());
-        return materials.add(material);
-    }
-
-    let handle = materials.add(material);
-
-    // Cache miss (or no cache): dispatch generation if a generator is selected.
-    if let Some(pending) = settings.texture.spawn(width, height) {
-        commands.spawn((
-            pending,
-            PatchMaterialTextures {
-                target: handle.clone(),
-                cache_key,
-            },
-        ));
-    }
-
+    let handle = materials.add(settings.to_standard_material());
+    request_material_textures(
+        commands,
+        materials,
+        images,
+        cache.as_deref(),
+        &settings.texture,
+        &handle,
+        width,
+        height,
+        settings.uses_parallax(),
+        settings.preview_size,
+    );
     handle
 }
 
+/// Counterpart of [`build_procedural_material_async`] for any
+/// [`TextureSlots`] material the caller already created — an
+/// `ExtendedMaterial`, or an app's own type: generate `settings.texture`
+/// at `width × height` (plus the height map and preview `settings` asks
+/// for) and patch it into `target`.
+///
+/// Only the textures come from `settings`; the PBR factors are the
+/// material's own business.  Needs
+/// [`ProceduralMaterialPlugin::<M>`](crate::ProceduralMaterialPlugin) so
+/// the tasks get patched.
+///
+/// Returns the task entity, or `None` when nothing was dispatched (cache
+/// hit, already patched, or [`TextureConfig::None`]).
+#[allow(clippy::too_many_arguments)]
+pub fn request_procedural_textures<M: TextureSlots>(
+    commands: &mut Commands,
+    materials: &mut Assets<M>,
+    images: &mut Assets<Image>,
+    cache: Option<&mut TextureCache>,
+    settings: &MaterialSettings,
+    target: &Handle<M>,
+    width: u32,
+    height: u32,
+) -> Option<Entity> {
+    request_material_textures(
+        commands,
+        materials,
+        images,
+        cache.as_deref(),
+        &settings.texture,
+        target,
+        width,
+        height,
+        settings.uses_parallax(),
+        settings.preview_size,
+    )
+}
+
 /// Bevy system — drains finished [`PendingTexture`]s tagged with
-/// [`PatchMaterialTextures`] and writes the generated images straight into
-/// the target material's albedo / normal / ORM slots.
+/// [`PatchMaterialTextures<M>`] and writes the generated images straight
+/// into the target material's [`TextureSlots`].
 ///
-/// Registered automatically by
-/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) alongside the
-/// generic [`poll_texture_tasks`](crate::async_gen::poll_texture_tasks).
-/// The two systems are mutually exclusive: an entity with
-/// `PatchMaterialTextures` is consumed here and never reaches the generic
-/// poller (which only handles bare `PendingTexture` entities).
-pub fn patch_procedural_material_textures(
+/// Registered automatically for [`StandardMaterial`] by
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin), and for other
+/// types by [`ProceduralMaterialPlugin`](crate::ProceduralMaterialPlugin),
+/// alongside the generic
+/// [`poll_texture_tasks`](crate::async_gen::poll_texture_tasks).  They are
+/// mutually exclusive: an entity with `PatchMaterialTextures` is consumed
+/// here and never reaches the generic poller (which only handles bare
+/// `PendingTexture` entities).  Both upload only what
+/// [`plan_texture_uploads`](crate::async_gen::plan_texture_uploads)
+/// admitted into the shared [`UploadBudget`], so [`TexturePriority`]
+/// order holds across them; run this system in
+/// [`TextureUploadSystems::Upload`](crate::async_gen::TextureUploadSystems::Upload).
+pub fn patch_procedural_material_textures<M: TextureSlots>(
     mut commands: Commands,
-    tasks: Query<(Entity, &PendingTexture, &PatchMaterialTextures)>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    tasks: Query<(
+        Entity,
+        &PendingTexture,
+        &PatchMaterialTextures<M>,
+        Option<&PreviewOf>,
+    )>,
+    mut materials: ResMut<Assets<M>>,
     mut images: ResMut<Assets<Image>>,
     mut cache: Option<ResMut<TextureCache>>,
+    mut budget: Option<ResMut<UploadBudget>>,
 ) {
     use std::sync::mpsc::TryRecvError;
 
-    for (entity, pending, patch) in &tasks {
-        let poll = pending
-            .rx
-            .lock()
-            .expect("texture thread poisoned")
-            .try_recv();
-
-        match poll {
-            Ok(Ok(map)) => {
+    for (entity, pending, patch, preview_of) in by_priority(tasks.iter(), |task| task.1) {
+        if let Some(PreviewOf(full)) = preview_of
+            && !tasks.contains(*full)
+        {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let admitted = take_admission(budget.as_deref_mut(), entity);
+        match pending.try_take(admitted) {
+            Ok(Ok(generated)) => {
                 let is_card = pending.is_card();
 
                 // Persist raw pixels for disk-backed stores while the map is
-                // still available — the upload below consumes it.
+                // still available — the upload below consumes it.  A map
+                // read back from the store is already on disk.
                 if let Some(cache_ref) = cache.as_deref()
                     && let Some(key) = patch.cache_key.as_ref()
+                    && !generated.from_cache
                 {
-                    cache_ref.persist_pixels(key, &map, is_card);
+                    cache_ref.record_generation(key.kind, generated.elapsed);
+                    cache_ref.persist_pixels(key, &generated.map, is_card);
                 }
 
-                let handles = if is_card {
-                    map_to_images_card(map, &mut images)
-                } else {
-                    map_to_images(map, &mut images)
-                };
+                let handles = generated.upload(is_card, &mut images);
+                pending.run_ready_callback(&handles, &mut commands);
 
                 if let Some(cache_ref) = cache.as_deref_mut()
                     && let Some(key) = patch.cache_key.clone()
@@ -492,29 +1241,57 @@ pub fn patch_procedural_material_textures(
                 }
 
                 if let Some(mat) = materials.get_mut(&patch.target) {
-                    mat.base_color_texture = Some(handles.albedo);
-                    mat.normal_map_texture = Some(handles.normal);
-                    mat.metallic_roughness_texture = Some(handles.roughness);
-                    // Defaults the emissive factor to white when a glow map
-                    // is present (and undoes it when one is not), so the map
-                    // is visible without the caller configuring emission.
-                    apply_emissive_map(mat, handles.emissive);
+                    apply_generated_handles(mat, handles);
                 }
+                commands.trigger(MaterialTexturesPatched {
+                    entity,
+                    target: patch.target.clone(),
+                    preview: preview_of.is_some(),
+                });
                 commands.entity(entity).despawn();
             }
-            Ok(Err(e)) => {
-                bevy::log::error!("Procedural material texture generation failed: {e}");
+            // A failed preview only means waiting for the full task, which
+            // reports its own outcome.
+            Ok(Err(failure)) if preview_of.is_some() => {
+                bevy::log::warn!("Procedural material preview: {failure}");
                 commands.entity(entity).despawn();
             }
-            Err(TryRecvError::Disconnected) => {
-                bevy::log::error!("Procedural material texture thread panicked");
+            Err(TryRecvError::Disconnected) if preview_of.is_some() => {
+                bevy::log::warn!("Procedural material preview: {}", TextureFailure::Lost);
                 commands.entity(entity).despawn();
             }
+            Ok(Err(failure)) => {
+                if !matches!(failure, TextureFailure::Cancelled) {
+                    bevy::log::error!("Procedural material textures: {failure}");
+                }
+                fail_patch(&mut commands, entity, patch, failure);
+            }
+            Err(TryRecvError::Disconnected) => {
+                bevy::log::error!("Procedural material textures: {}", TextureFailure::Lost);
+                fail_patch(&mut commands, entity, patch, TextureFailure::Lost);
+            }
             Err(TryRecvError::Empty) => {}
         }
     }
 }
 
+/// Report a failed patch task through [`MaterialTexturesFailed`], then
+/// despawn it.
+fn fail_patch<M: TextureSlots>(
+    commands: &mut Commands,
+    entity: Entity,
+    patch: &PatchMaterialTextures<M>,
+    failure: TextureFailure,
+) {
+    commands.trigger(MaterialTexturesFailed {
+        entity,
+        target: patch.target.clone(),
+        cache_key: patch.cache_key.clone(),
+        failure,
+    });
+    commands.entity(entity).despawn();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -645,9 +1422,9 @@ mod tests {
     }
 
     /// The system params `build_procedural_material_async` consumes.
-    type BuilderParams = (
+    type BuilderParams<M = StandardMaterial> = (
         Commands<'static, 'static>,
-        ResMut<'static, Assets<StandardMaterial>>,
+        ResMut<'static, Assets<M>>,
         ResMut<'static, Assets<Image>>,
     );
 
@@ -691,9 +1468,414 @@ mod tests {
         state.apply(&mut world);
     }
 
+    /// The extended PBR factors and UV rotation / offset land on the
+    /// `StandardMaterial`.
+    #[test]
+    fn extended_pbr_settings_are_applied() {
+        let settings = MaterialSettings {
+            uv_scale: 2.0,
+            uv_rotation: std::f32::consts::FRAC_PI_2,
+            uv_offset: [0.25, 0.5],
+            reflectance: 0.3,
+            clearcoat: 0.8,
+            clearcoat_roughness: 0.1,
+            anisotropy_strength: 0.6,
+            anisotropy_rotation: 1.0,
+            specular_transmission: 0.9,
+            thickness: 0.2,
+            ior: 1.31,
+            ..MaterialSettings::default()
+        };
+        let mat = settings.to_standard_material();
+        assert_eq!(mat.reflectance, 0.3);
+        assert_eq!(mat.clearcoat, 0.8);
+        assert_eq!(mat.clearcoat_perceptual_roughness, 0.1);
+        assert_eq!(mat.anisotropy_strength, 0.6);
+        assert_eq!(mat.anisotropy_rotation, 1.0);
+        assert_eq!(mat.specular_transmission, 0.9);
+        assert_eq!(mat.thickness, 0.2);
+        assert_eq!(mat.ior, 1.31);
+
+        // Scale, then rotate a quarter turn, then offset.
+        let uv = mat.uv_transform.transform_point2(Vec2::new(1.0, 0.0));
+        assert!((uv - Vec2::new(0.25, 2.5)).length() < 1e-5);
+    }
+
+    /// `recommended_for` keeps the config, passes the maps through, and
+    /// adds the per-generator factors the maps cannot carry.
+    #[test]
+    fn recommended_settings_follow_the_generator() {
+        use crate::ice::IceConfig;
+        use crate::lava::LavaConfig;
+        use crate::metal::MetalConfig;
+
+        let brick =
+            MaterialSettings::recommended_for(&TextureConfig::Brick(BrickConfig::default()));
+        assert_eq!(brick.base_color, [1.0, 1.0, 1.0]);
+        assert_eq!(brick.roughness, 1.0);
+        assert_eq!(brick.metallic, 0.0);
+        assert_eq!(brick.specular_transmission, 0.0);
+        assert_eq!(brick.emission_strength, 0.0);
+        assert!(matches!(brick.texture, TextureConfig::Brick(_)));
+
+        let metal =
+            MaterialSettings::recommended_for(&TextureConfig::Metal(MetalConfig::default()));
+        assert_eq!(metal.metallic, 1.0);
+
+        let ice = MaterialSettings::recommended_for(&TextureConfig::Ice(IceConfig::default()));
+        assert!(ice.specular_transmission > 0.0);
+        assert_eq!(ice.ior, 1.31);
+
+        // Lava glows, but only through its map: the untextured material
+        // multiplies the factor by the black placeholder.
+        let lava = MaterialSettings::recommended_for(&TextureConfig::Lava(LavaConfig::default()));
+        assert_eq!(lava.emission_strength, 2.0);
+        assert!(lava.emission_needs_map);
+        let mat = lava.to_standard_material();
+        assert_eq!(mat.emissive.red, 2.0);
+        assert_eq!(mat.emissive_texture, Some(EMISSIVE_PLACEHOLDER));
+
+        // Every generator gets a usable starting point.
+        for config in TextureConfig::all_defaults() {
+            let settings = MaterialSettings::recommended_for(&config);
+            assert_eq!(settings.texture.fingerprint(), config.fingerprint());
+            assert_eq!(settings.preview_size, 0);
+            assert!(!settings.use_recommended);
+        }
+    }
+
+    /// `use_recommended` swaps in the preset's factors but keeps the
+    /// caller's UV and parallax fields.
+    #[test]
+    fn use_recommended_applies_the_preset() {
+        use crate::metal::MetalConfig;
+
+        let settings = MaterialSettings {
+            texture: TextureConfig::Metal(MetalConfig::default()),
+            use_recommended: true,
+            metallic: 0.0,
+            uv_scale: 3.0,
+            parallax_depth_scale: 0.04,
+            ..MaterialSettings::default()
+        };
+        let mat = settings.to_standard_material();
+        assert_eq!(mat.metallic, 1.0);
+        assert_eq!(mat.perceptual_roughness, 1.0);
+        assert_eq!(mat.parallax_depth_scale, 0.04);
+        assert_eq!(mat.uv_transform, Affine2::from_scale(Vec2::splat(3.0)));
+
+        let plain = MaterialSettings {
+            use_recommended: false,
+            ..settings
+        };
+        assert_eq!(plain.to_standard_material().metallic, 0.0);
+    }
+
+    /// Parallax fields land on the `StandardMaterial`; `uses_parallax`
+    /// needs both a positive depth scale and a texture to derive it from.
+    #[test]
+    fn parallax_settings_are_applied() {
+        let settings = MaterialSettings {
+            parallax_depth_scale: 0.05,
+            parallax_mapping_method: ParallaxMethod::Relief { max_steps: 4 },
+            texture: TextureConfig::Brick(BrickConfig::default()),
+            ..MaterialSettings::default()
+        };
+        assert!(settings.uses_parallax());
+        let mat = settings.to_standard_material();
+        assert_eq!(mat.parallax_depth_scale, 0.05);
+        assert_eq!(
+            mat.parallax_mapping_method,
+            ParallaxMappingMethod::Relief { max_steps: 4 }
+        );
+
+        let untextured = MaterialSettings {
+            texture: TextureConfig::None,
+            ..settings
+        };
+        assert!(!untextured.uses_parallax());
+        assert!(!MaterialSettings::default().uses_parallax());
+    }
+
+    /// A custom material receives the maps it has slots for through its
+    /// own patch system; the `StandardMaterial` patcher and the generic
+    /// poller leave its task alone.
+    #[test]
+    fn custom_material_receives_generated_albedo() {
+        #[derive(Asset, TypePath)]
+        struct AlbedoOnly {
+            texture: Option<Handle<Image>>,
+        }
+
+        impl TextureSlots for AlbedoOnly {
+            fn set_albedo(&mut self, albedo: Handle<Image>) {
+                self.texture = Some(albedo);
+            }
+
+            fn has_albedo(&self) -> bool {
+                self.texture.is_some()
+            }
+        }
+
+        let settings = MaterialSettings {
+            texture: TextureConfig::Bark(BarkConfig::default()),
+            ..MaterialSettings::default()
+        };
+
+        let mut world = asset_world();
+        world.insert_resource(Assets::<AlbedoOnly>::default());
+        let mut state: SystemState<BuilderParams<AlbedoOnly>> = SystemState::new(&mut world);
+        let (mut commands, mut materials, mut images) = state.get_mut(&mut world);
+        let handle = materials.add(AlbedoOnly { texture: None });
+        let task = request_procedural_textures(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            None,
+            &settings,
+            &handle,
+            16,
+            16,
+        );
+        assert!(task.is_some());
+        state.apply(&mut world);
+
+        let mut schedule = bevy::ecs::schedule::Schedule::default();
+        schedule.add_systems((
+            crate::async_gen::poll_texture_tasks,
+            patch_procedural_material_textures::<StandardMaterial>,
+            patch_procedural_material_textures::<AlbedoOnly>,
+        ));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while !world
+            .resource::<Assets<AlbedoOnly>>()
+            .get(&handle)
+            .unwrap()
+            .has_albedo()
+        {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "texture generation timed out"
+            );
+            schedule.run(&mut world);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let mut ready = world.query::<&crate::async_gen::TextureReady>();
+        assert_eq!(ready.iter(&world).count(), 0);
+    }
+
+    /// Previews keep the aspect ratio and only apply when smaller.
+    #[test]
+    fn preview_dimensions_scale_the_longer_side() {
+        assert_eq!(preview_dimensions(1024, 512, 64), Some((64, 32)));
+        assert_eq!(preview_dimensions(512, 1024, 64), Some((32, 64)));
+        assert_eq!(preview_dimensions(4096, 8, 64), Some((64, 1)));
+        assert_eq!(preview_dimensions(64, 64, 64), None);
+        assert_eq!(preview_dimensions(1024, 1024, 0), None);
+    }
+
+    /// With `preview_size` set, the preview is patched in before the full
+    /// texture, which then replaces it.
+    #[test]
+    fn preview_is_patched_before_the_full_texture() {
+        #[derive(bevy::ecs::resource::Resource, Default)]
+        struct Patched(Vec<bool>);
+
+        let settings = MaterialSettings {
+            texture: TextureConfig::Bark(BarkConfig::default()),
+            preview_size: 16,
+            ..MaterialSettings::default()
+        };
+
+        let mut world = asset_world();
+        world.init_resource::<Patched>();
+        world.add_observer(
+            |patched: bevy::ecs::observer::On<MaterialTexturesPatched>,
+             mut log: ResMut<Patched>| log.0.push(patched.preview),
+        );
+        let mut state: SystemState<BuilderParams> = SystemState::new(&mut world);
+        let (mut commands, mut materials, mut images) = state.get_mut(&mut world);
+        let handle = build_procedural_material_async(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            None,
+            &settings,
+            256,
+            256,
+        );
+        state.apply(&mut world);
+
+        let mut schedule = bevy::ecs::schedule::Schedule::default();
+        schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while !world.resource::<Patched>().0.contains(&false) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "texture generation timed out"
+            );
+            schedule.run(&mut world);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert_eq!(world.resource::<Patched>().0, [true, false]);
+        let albedo = world
+            .resource::<Assets<StandardMaterial>>()
+            .get(&handle)
+            .and_then(|m| m.base_color_texture.clone())
+            .expect("albedo bound");
+        let image = world.resource::<Assets<Image>>().get(&albedo).unwrap();
+        assert_eq!(image.width(), 256);
+    }
+
+    /// A preview whose full-resolution task is gone is dropped unapplied.
+    #[test]
+    fn preview_is_dropped_with_its_full_task() {
+        let settings = MaterialSettings {
+            texture: TextureConfig::Bark(BarkConfig::default()),
+            preview_size: 16,
+            ..MaterialSettings::default()
+        };
+
+        let mut world = asset_world();
+        let mut state: SystemState<BuilderParams> = SystemState::new(&mut world);
+        let (mut commands, mut materials, mut images) = state.get_mut(&mut world);
+        let handle = build_procedural_material_async(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            None,
+            &settings,
+            256,
+            256,
+        );
+        state.apply(&mut world);
+
+        let mut full = world.query_filtered::<Entity, (
+            bevy::ecs::query::With<PatchMaterialTextures>,
+            bevy::ecs::query::Without<PreviewOf>,
+        )>();
+        let full = full.single(&world).unwrap();
+        world.despawn(full);
+
+        let mut schedule = bevy::ecs::schedule::Schedule::default();
+        schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
+        schedule.run(&mut world);
+
+        let mut tasks = world.query::<&PatchMaterialTextures>();
+        assert_eq!(tasks.iter(&world).count(), 0);
+        let material = world
+            .resource::<Assets<StandardMaterial>>()
+            .get(&handle)
+            .unwrap();
+        assert!(material.base_color_texture.is_none());
+    }
+
+    /// A parallax material gets its generated height map bound as
+    /// `depth_map` by the patch system.
+    #[test]
+    fn parallax_material_receives_depth_map() {
+        let settings = MaterialSettings {
+            parallax_depth_scale: 0.05,
+            texture: TextureConfig::Brick(BrickConfig::default()),
+            ..MaterialSettings::default()
+        };
+
+        let mut world = asset_world();
+        let mut state: SystemState<BuilderParams> = SystemState::new(&mut world);
+        let (mut commands, mut materials, mut images) = state.get_mut(&mut world);
+        let handle = build_procedural_material_async(
+            &mut commands,
+            &mut materials,
+            &mut images,
+            None,
+            &settings,
+            16,
+            16,
+        );
+        state.apply(&mut world);
+
+        let mut schedule = bevy::ecs::schedule::Schedule::default();
+        schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        loop {
+            schedule.run(&mut world);
+            let patched = world
+                .resource::<Assets<StandardMaterial>>()
+                .get(&handle)
+                .is_some_and(|m| m.base_color_texture.is_some());
+            if patched {
+                break;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "texture generation timed out"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let mat = world
+            .resource::<Assets<StandardMaterial>>()
+            .get(&handle)
+            .unwrap();
+        let depth = mat.depth_map.clone().expect("depth map bound");
+        assert!(world.resource::<Assets<Image>>().get(&depth).is_some());
+    }
+
+    /// An `on_ready` callback on a patch task runs with the handles that
+    /// end up in the material.
+    #[test]
+    fn on_ready_runs_for_patch_tasks() {
+        #[derive(Component)]
+        struct Seen(Handle<Image>);
+
+        let mut world = asset_world();
+        let handle = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let pending = TextureConfig::Bark(BarkConfig::default())
+            .spawn(16, 16)
+            .unwrap()
+            .on_ready(|handles, commands| {
+                commands.spawn(Seen(handles.albedo.clone()));
+            });
+        world.spawn((
+            pending,
+            PatchMaterialTextures {
+                target: handle.clone(),
+                cache_key: None,
+            },
+        ));
+
+        let mut schedule = bevy::ecs::schedule::Schedule::default();
+        schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        let albedo = loop {
+            schedule.run(&mut world);
+            let albedo = world
+                .resource::<Assets<StandardMaterial>>()
+                .get(&handle)
+                .and_then(|m| m.base_color_texture.clone());
+            if let Some(albedo) = albedo {
+                break albedo;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "texture generation timed out"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        };
+
+        let mut seen = world.query::<&Seen>();
+        let seen: Vec<_> = seen.iter(&world).map(|seen| seen.0.id()).collect();
+        assert_eq!(seen, [albedo.id()]);
+    }
+
     /// End-to-end FileStore regression test through the real plugin systems:
     /// pass 1 generates and persists to disk; pass 2 (fresh world, same
-    /// directory) must hit the blob synchronously and dispatch no task.
+    /// directory) must leave the caller's system untouched and serve the
+    /// blob through the background task instead of regenerating.
     #[test]
     fn file_cache_round_trips_through_material_flow() {
         let dir = std::env::temp_dir().join(format!("bst-matflow-{}", std::process::id()));
@@ -723,7 +1905,7 @@ mod tests {
             state.apply(&mut world);
 
             let mut schedule = bevy::ecs::schedule::Schedule::default();
-            schedule.add_systems(patch_procedural_material_textures);
+            schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
             let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
             loop {
                 schedule.run(&mut world);
@@ -742,10 +1924,17 @@ mod tests {
             }
         }
 
-        let blobs = std::fs::read_dir(&dir).expect("cache dir readable").count();
+        let blobs = std::fs::read_dir(&dir)
+            .expect("cache dir readable")
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .is_ok_and(|entry| entry.path().extension().is_some_and(|ext| ext == "bin"))
+            })
+            .count();
         assert_eq!(blobs, 1, "FileStore must have persisted exactly one blob");
 
-        // Pass 2: fresh world, same directory — synchronous disk hit.
+        // Pass 2: fresh world, same directory — asynchronous disk hit.
         {
             let mut world = asset_world();
             let mut cache = TextureCache::file(dir.clone(), 0).expect("reopen cache dir");
@@ -761,21 +1950,29 @@ mod tests {
                 8,
                 8,
             );
-            let mat = materials.get(&handle).expect("material registered");
-            assert!(
-                mat.base_color_texture.is_some(),
-                "disk hit must populate the albedo slot synchronously"
-            );
-            assert!(mat.normal_map_texture.is_some());
-            assert!(mat.metallic_roughness_texture.is_some());
+            assert_eq!(images.len(), 0, "the disk read must not block the caller");
             state.apply(&mut world);
 
             let mut pending = world.query::<&PendingTexture>();
-            assert_eq!(
-                pending.iter(&world).count(),
-                0,
-                "a cache hit must not dispatch a generation task"
-            );
+            let task = pending.single(&world).expect("one load task");
+            let generated = task
+                .rx
+                .lock()
+                .unwrap()
+                .recv_timeout(std::time::Duration::from_secs(30))
+                .expect("load finished")
+                .expect("load succeeded");
+            assert!(generated.from_cache, "a disk hit must not regenerate");
+
+            let mut images = world.resource_mut::<Assets<Image>>();
+            let handles = generated.upload(false, &mut images);
+            assert!(handles.height.is_none());
+            let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+            apply_generated_handles(materials.get_mut(&handle).unwrap(), handles);
+            let mat = materials.get(&handle).unwrap();
+            assert!(mat.base_color_texture.is_some());
+            assert!(mat.normal_map_texture.is_some());
+            assert!(mat.metallic_roughness_texture.is_some());
         }
 
         let _ = std::fs::remove_dir_all(&dir);
@@ -825,6 +2022,27 @@ mod tests {
         assert!(mat.emissive_texture.is_none());
     }
 
+    #[test]
+    fn apply_emissive_map_masks_a_custom_factor_when_the_map_drops() {
+        let glow = LinearRgba::new(2.0, 2.0, 2.0, 1.0);
+        let mut mat = StandardMaterial {
+            emissive: glow,
+            ..Default::default()
+        };
+        apply_emissive_map(&mut mat, Some(dummy_image_handle()));
+        apply_emissive_map(&mut mat, None);
+        assert_eq!(mat.emissive, glow, "the factor is the caller's");
+        assert_eq!(mat.emissive_texture, Some(EMISSIVE_PLACEHOLDER));
+
+        // Without a map to begin with, the factor is a plain flat emission.
+        let mut flat = StandardMaterial {
+            emissive: glow,
+            ..Default::default()
+        };
+        apply_emissive_map(&mut flat, None);
+        assert!(flat.emissive_texture.is_none());
+    }
+
     /// End-to-end: a lava material built with default `MaterialSettings`
     /// (no emission configured) must end up with both an emissive texture
     /// and a non-black emissive factor after the patch system runs, so the
@@ -866,7 +2084,7 @@ mod tests {
         state.apply(&mut world);
 
         let mut schedule = bevy::ecs::schedule::Schedule::default();
-        schedule.add_systems(patch_procedural_material_textures);
+        schedule.add_systems(patch_procedural_material_textures::<StandardMaterial>);
         let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
         loop {
             schedule.run(&mut world);
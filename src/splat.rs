@@ -0,0 +1,525 @@
+//! Terrain splat material: several generated surfaces on one mesh.
+//!
+//! [`SplatMaterial`] extends [`StandardMaterial`] with up to
+//! [`MAX_SPLAT_LAYERS`] generated surface layers, bound as the albedo /
+//! normal / ORM texture arrays built by [`PendingTextureArray`].  The
+//! fragment shader blends the layers per pixel, either by an RGBA splat
+//! weight image (R = layer 0 … A = layer 3, sampled with the mesh UVs) or
+//! by per-layer [`SplatRule`]s on world height and slope, and lights the
+//! result through the regular PBR path.
+//!
+//! Layers are described by the same [`MaterialSettings`] as single-texture
+//! materials — `base_color` tints the layer, `roughness` / `metallic`
+//! scale its ORM map and `uv_scale` sets its tiling:
+//!
+//! ```rust,ignore
+//! app.add_plugins((SymbiosTexturePlugin::default(), SplatMaterialPlugin));
+//!
+//! let layers = [
+//!     SplatLayer::new(grass_settings).with_rule(SplatRule { slope: Some((0.0, 0.3)), ..default() }),
+//!     SplatLayer::new(rock_settings).with_rule(SplatRule { slope: Some((0.3, 1.0)), ..default() }),
+//!     SplatLayer::new(snow_settings).with_rule(SplatRule { height: Some((40.0, 1000.0)), ..default() }),
+//! ];
+//! let material = build_splat_material_async(
+//!     &mut commands, &mut splat_materials, &layers, SplatWeights::Rules, 1024, 1024,
+//! ).expect("1–4 textured layers");
+//! commands.spawn((Mesh3d(terrain), MeshMaterial3d(material)));
+//! ```
+//!
+//! The material renders with Bevy's fallback textures until the arrays are
+//! ready, then [`patch_splat_material_textures`] swaps them in on the same
+//! handle; if generation fails it triggers [`SplatTexturesFailed`] instead
+//! and the material stays on the fallbacks.  Emission settings of the
+//! layers are ignored.  Meshes with vertex tangents get mikktspace normal
+//! mapping; without them the shader derives a tangent frame from
+//! screen-space derivatives.  Layers and the weight map are sampled with
+//! the mesh UVs; a mesh without `ATTRIBUTE_UV_0` is mapped by world-space
+//! XZ instead, one texture repeat per world unit before `uv_scale`.
+
+use bevy::{
+    app::{App, Plugin, Update},
+    asset::{Asset, Assets, Handle, embedded_asset},
+    color::{Color, ColorToComponents},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EntityEvent,
+        system::{Commands, Query, ResMut},
+    },
+    image::Image,
+    math::Vec4,
+    pbr::{ExtendedMaterial, MaterialExtension, MaterialPlugin, StandardMaterial},
+    reflect::Reflect,
+    render::render_resource::{AsBindGroup, ShaderType},
+    shader::ShaderRef,
+};
+
+use crate::array::{PendingTextureArray, TextureArrayReady};
+use crate::async_gen::{TextureFailed, TextureFailure};
+use crate::material::MaterialSettings;
+
+/// Maximum number of layers one [`SplatMaterial`] blends — one per channel
+/// of the RGBA splat weight image.
+pub const MAX_SPLAT_LAYERS: usize = 4;
+
+/// Asset path of the embedded splat fragment shader.
+const SPLAT_SHADER_PATH: &str = "embedded://bevy_symbios_texture/splat.wgsl";
+
+/// [`StandardMaterial`] extended with generated splat layers.
+pub type SplatMaterial = ExtendedMaterial<StandardMaterial, SplatExtension>;
+
+/// Height / slope placement of one layer when blending by
+/// [`SplatWeights::Rules`].
+///
+/// A layer's weight is 1 inside every enabled range and fades to 0 over
+/// `blend` outside it; overlapping layers are normalised against each
+/// other.  Disabled (`None`) ranges do not restrict the layer.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SplatRule {
+    /// World-space Y range `(min, max)`.
+    #[serde(default)]
+    pub height: Option<(f32, f32)>,
+    /// Slope range `(min, max)`, from `0.0` (flat) to `1.0` (vertical),
+    /// computed as `1 - normal.y`.
+    #[serde(default)]
+    pub slope: Option<(f32, f32)>,
+    /// Fade width outside each range, in the range's own units.
+    #[serde(default)]
+    pub blend: f32,
+}
+
+/// One generated surface layer of a [`SplatMaterial`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SplatLayer {
+    /// Texture config plus tint / roughness / metallic / tiling.  The
+    /// config must not be [`TextureConfig::None`](crate::TextureConfig::None).
+    pub settings: MaterialSettings,
+    /// Placement used with [`SplatWeights::Rules`]; ignored for weight maps.
+    #[serde(default)]
+    pub rule: SplatRule,
+}
+
+impl SplatLayer {
+    /// A layer covering everything (no rule restrictions).
+    pub fn new(settings: MaterialSettings) -> Self {
+        Self {
+            settings,
+            rule: SplatRule::default(),
+        }
+    }
+
+    /// Replace the placement rule.
+    pub fn with_rule(mut self, rule: SplatRule) -> Self {
+        self.rule = rule;
+        self
+    }
+}
+
+/// How a [`SplatMaterial`] weights its layers.
+#[derive(Clone, Debug)]
+pub enum SplatWeights {
+    /// Per-texel weights from an RGBA image sampled with the mesh UVs (or
+    /// world XZ on meshes without UVs): R weights layer 0, G layer 1, B
+    /// layer 2, A layer 3.  Weights need not
+    /// sum to one; they are normalised in the shader.
+    Map(Handle<Image>),
+    /// Per-layer [`SplatRule`]s on world height and slope.
+    Rules,
+}
+
+/// Per-layer shader parameters.  Mirrors `SplatLayerParams` in
+/// `splat.wgsl`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ShaderType, Reflect)]
+pub struct SplatLayerParams {
+    /// Linear RGB albedo multiplier; alpha unused.
+    pub tint: Vec4,
+    /// `x` roughness factor, `y` metallic factor, `z` UV scale.
+    pub pbr: Vec4,
+    /// `x..y` height range, `z` fade width, `w` 1 when enabled.
+    pub height: Vec4,
+    /// `x..y` slope range, `z` fade width, `w` 1 when enabled.
+    pub slope: Vec4,
+}
+
+/// Uniform block of a [`SplatExtension`].  Mirrors `SplatParams` in
+/// `splat.wgsl`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ShaderType, Reflect)]
+pub struct SplatParams {
+    /// Parameters of each layer; entries past `layer_count` are unused.
+    pub layers: [SplatLayerParams; MAX_SPLAT_LAYERS],
+    /// Number of active layers.
+    pub layer_count: u32,
+    /// `1` to weight by the splat map, `0` to weight by rules.
+    pub use_weight_map: u32,
+}
+
+impl SplatParams {
+    /// Pack `layers` (at most [`MAX_SPLAT_LAYERS`]; extras are ignored) for
+    /// the shader.
+    pub fn from_layers(layers: &[SplatLayer], use_weight_map: bool) -> Self {
+        let range = |range: Option<(f32, f32)>, blend: f32| match range {
+            Some((min, max)) => Vec4::new(min, max, blend, 1.0),
+            None => Vec4::ZERO,
+        };
+        let mut params = Self {
+            layer_count: layers.len().min(MAX_SPLAT_LAYERS) as u32,
+            use_weight_map: use_weight_map as u32,
+            ..Default::default()
+        };
+        for (slot, layer) in params.layers.iter_mut().zip(layers) {
+            let s = &layer.settings;
+            *slot = SplatLayerParams {
+                tint: Color::srgb_from_array(s.base_color).to_linear().to_vec4(),
+                pbr: Vec4::new(s.roughness, s.metallic, s.uv_scale, 0.0),
+                height: range(layer.rule.height, layer.rule.blend),
+                slope: range(layer.rule.slope, layer.rule.blend),
+            };
+        }
+        params
+    }
+}
+
+/// [`MaterialExtension`] holding the splat layer arrays and weights.
+///
+/// Texture slots left `None` bind Bevy's fallback images; the arrays are
+/// filled in by [`patch_splat_material_textures`] once generated.
+#[derive(Asset, AsBindGroup, Reflect, Clone, Debug, Default)]
+pub struct SplatExtension {
+    /// Layer parameters.
+    #[uniform(100)]
+    pub params: SplatParams,
+    /// sRGB albedo array, one layer per [`SplatLayer`].
+    #[texture(101, dimension = "2d_array")]
+    #[sampler(102)]
+    pub albedo: Option<Handle<Image>>,
+    /// Linear normal-map array.
+    #[texture(103, dimension = "2d_array")]
+    pub normal: Option<Handle<Image>>,
+    /// Linear ORM array.
+    #[texture(104, dimension = "2d_array")]
+    pub orm: Option<Handle<Image>>,
+    /// RGBA splat weight image for [`SplatWeights::Map`].
+    #[texture(105)]
+    #[sampler(106)]
+    pub weights: Option<Handle<Image>>,
+}
+
+impl MaterialExtension for SplatExtension {
+    fn fragment_shader() -> ShaderRef {
+        SPLAT_SHADER_PATH.into()
+    }
+
+    fn deferred_fragment_shader() -> ShaderRef {
+        SPLAT_SHADER_PATH.into()
+    }
+}
+
+/// Marker on a [`PendingTextureArray`] entity whose arrays should be
+/// patched onto a [`SplatMaterial`].
+///
+/// Spawned by [`build_splat_material_async`], consumed by
+/// [`patch_splat_material_textures`].  Despawning the entity first cancels
+/// generation and leaves the material on its fallback textures.
+#[derive(Component)]
+pub struct PatchSplatTextures {
+    /// Material whose extension receives the generated arrays.
+    pub target: Handle<SplatMaterial>,
+}
+
+/// Triggered by [`patch_splat_material_textures`] when a
+/// [`PatchSplatTextures`] task fails, just before its entity is despawned.
+/// The material keeps Bevy's fallback textures:
+///
+/// ```rust,ignore
+/// app.add_observer(|failed: On<SplatTexturesFailed>| {
+///     warn!("{:?} stays untextured: {}", failed.target, failed.failure);
+/// });
+/// ```
+#[derive(EntityEvent, Debug)]
+pub struct SplatTexturesFailed {
+    /// The task entity, despawned once observers have run.
+    pub entity: Entity,
+    /// [`PatchSplatTextures::target`] of the failed task.
+    pub target: Handle<SplatMaterial>,
+    /// What went wrong.
+    pub failure: TextureFailure,
+}
+
+/// Create a [`SplatMaterial`] for `layers` and start generating its texture
+/// arrays at `width × height`.
+///
+/// Returns the material handle immediately; the arrays are patched in by
+/// [`patch_splat_material_textures`].  Returns `None` — adding nothing —
+/// when `layers` is empty, has more than [`MAX_SPLAT_LAYERS`] entries, or
+/// a layer's config is `TextureConfig::None`.
+pub fn build_splat_material_async(
+    commands: &mut Commands,
+    materials: &mut Assets<SplatMaterial>,
+    layers: &[SplatLayer],
+    weights: SplatWeights,
+    width: u32,
+    height: u32,
+) -> Option<Handle<SplatMaterial>> {
+    if layers.len() > MAX_SPLAT_LAYERS {
+        return None;
+    }
+    let configs: Vec<_> = layers.iter().map(|l| l.settings.texture.clone()).collect();
+    let pending = PendingTextureArray::new(&configs, width, height)?;
+
+    let (weight_map, use_weight_map) = match weights {
+        SplatWeights::Map(image) => (Some(image), true),
+        SplatWeights::Rules => (None, false),
+    };
+    let target = materials.add(SplatMaterial {
+        base: StandardMaterial::default(),
+        extension: SplatExtension {
+            params: SplatParams::from_layers(layers, use_weight_map),
+            weights: weight_map,
+            ..Default::default()
+        },
+    });
+    commands.spawn((
+        pending,
+        PatchSplatTextures {
+            target: target.clone(),
+        },
+    ));
+    Some(target)
+}
+
+/// Bevy system — writes finished texture arrays into their target
+/// [`SplatMaterial`] and despawns the task entity.  A task that failed
+/// triggers [`SplatTexturesFailed`] and is despawned the same way.
+///
+/// Registered by [`SplatMaterialPlugin`].  Relies on
+/// [`poll_texture_array_tasks`](crate::array::poll_texture_array_tasks)
+/// (registered by [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin))
+/// to produce the [`TextureArrayReady`] / [`TextureFailed`] it consumes.
+pub fn patch_splat_material_textures(
+    mut commands: Commands,
+    ready: Query<(Entity, &TextureArrayReady, &PatchSplatTextures)>,
+    mut failed: Query<(Entity, &mut TextureFailed, &PatchSplatTextures)>,
+    mut materials: ResMut<Assets<SplatMaterial>>,
+) {
+    for (entity, mut failed, patch) in &mut failed {
+        // The entity is despawned below, so the failure can be moved out.
+        let failure = std::mem::replace(&mut failed.0, TextureFailure::Lost);
+        commands.trigger(SplatTexturesFailed {
+            entity,
+            target: patch.target.clone(),
+            failure,
+        });
+        commands.entity(entity).despawn();
+    }
+    for (entity, TextureArrayReady(arrays), patch) in &ready {
+        if let Some(material) = materials.get_mut(&patch.target) {
+            material.extension.albedo = Some(arrays.albedo.clone());
+            material.extension.normal = Some(arrays.normal.clone());
+            material.extension.orm = Some(arrays.roughness.clone());
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Registers [`SplatMaterial`], its embedded shader and
+/// [`patch_splat_material_textures`].
+///
+/// Needs Bevy's render plugins, so it is separate from
+/// [`SymbiosTexturePlugin`](crate::SymbiosTexturePlugin) — add both.
+pub struct SplatMaterialPlugin;
+
+impl Plugin for SplatMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "splat.wgsl");
+        app.add_plugins(MaterialPlugin::<SplatMaterial>::default())
+            .add_systems(Update, patch_splat_material_textures);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::ecs::schedule::{IntoScheduleConfigs, Schedule};
+    use bevy::ecs::system::SystemState;
+    use bevy::ecs::world::{CommandQueue, World};
+
+    use crate::ground::GroundConfig;
+    use crate::material::TextureConfig;
+    use crate::rock::RockConfig;
+
+    fn layer(texture: TextureConfig) -> SplatLayer {
+        SplatLayer::new(MaterialSettings {
+            texture,
+            ..MaterialSettings::default()
+        })
+    }
+
+    #[test]
+    fn packs_layer_params() {
+        let layers = [
+            layer(TextureConfig::Ground(GroundConfig::default())).with_rule(SplatRule {
+                slope: Some((0.0, 0.3)),
+                blend: 0.1,
+                ..Default::default()
+            }),
+            layer(TextureConfig::Rock(RockConfig::default())),
+        ];
+        let params = SplatParams::from_layers(&layers, false);
+        assert_eq!(params.layer_count, 2);
+        assert_eq!(params.use_weight_map, 0);
+        assert_eq!(params.layers[0].slope, Vec4::new(0.0, 0.3, 0.1, 1.0));
+        assert_eq!(params.layers[0].height.w, 0.0, "no height rule");
+        assert_eq!(params.layers[1].pbr, Vec4::new(0.5, 0.0, 1.0, 0.0));
+        // sRGB base colour arrives linearised.
+        let tint = params.layers[1].tint;
+        assert!(tint.x < 0.6 && tint.x > 0.3, "linear tint {tint}");
+        assert_eq!(params.layers[2], SplatLayerParams::default());
+    }
+
+    #[test]
+    fn rejects_unsupported_layer_lists() {
+        let mut world = World::new();
+        let mut materials = Assets::<SplatMaterial>::default();
+        let mut commands_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut commands_queue, &world);
+        let rock = layer(TextureConfig::Rock(RockConfig::default()));
+
+        let too_many = vec![rock.clone(); MAX_SPLAT_LAYERS + 1];
+        let none = [rock, layer(TextureConfig::None)];
+        for layers in [&[][..], &too_many[..], &none[..]] {
+            let built = build_splat_material_async(
+                &mut commands,
+                &mut materials,
+                layers,
+                SplatWeights::Rules,
+                8,
+                8,
+            );
+            assert!(built.is_none());
+        }
+        commands_queue.apply(&mut world);
+        assert_eq!(materials.len(), 0);
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn patches_arrays_into_material() {
+        let mut world = World::new();
+        world.insert_resource(Assets::<Image>::default());
+        world.insert_resource(Assets::<SplatMaterial>::default());
+
+        let layers = [
+            layer(TextureConfig::Ground(GroundConfig::default())),
+            layer(TextureConfig::Rock(RockConfig::default())),
+        ];
+        let weights = world.resource_mut::<Assets<Image>>().add(Image::default());
+        let mut system_state: SystemState<(Commands, ResMut<Assets<SplatMaterial>>)> =
+            SystemState::new(&mut world);
+        let (mut commands, mut materials) = system_state.get_mut(&mut world);
+        let target = build_splat_material_async(
+            &mut commands,
+            &mut materials,
+            &layers,
+            SplatWeights::Map(weights.clone()),
+            8,
+            8,
+        )
+        .expect("two textured layers");
+        system_state.apply(&mut world);
+
+        let material = world
+            .resource::<Assets<SplatMaterial>>()
+            .get(&target)
+            .unwrap();
+        assert_eq!(material.extension.params.use_weight_map, 1);
+        assert_eq!(material.extension.weights.as_ref(), Some(&weights));
+        assert!(material.extension.albedo.is_none(), "fallback until ready");
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                crate::array::poll_texture_array_tasks,
+                patch_splat_material_textures,
+            )
+                .chain(),
+        );
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        loop {
+            schedule.run(&mut world);
+            let material = world
+                .resource::<Assets<SplatMaterial>>()
+                .get(&target)
+                .unwrap();
+            if material.extension.albedo.is_some() {
+                break;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "splat arrays timed out"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let material = world
+            .resource::<Assets<SplatMaterial>>()
+            .get(&target)
+            .unwrap();
+        let orm = material.extension.orm.clone().expect("orm array");
+        let images = world.resource::<Assets<Image>>();
+        let orm = images.get(&orm).expect("uploaded");
+        assert_eq!(orm.texture_descriptor.size.depth_or_array_layers, 2);
+        assert!(material.extension.normal.is_some());
+        // The task entity is gone once patched.
+        schedule.run(&mut world);
+        assert!(
+            world
+                .query::<&PatchSplatTextures>()
+                .iter(&world)
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn reports_failed_arrays() {
+        #[derive(bevy::ecs::resource::Resource, Default)]
+        struct Failed(Vec<(Handle<SplatMaterial>, bool)>);
+
+        let mut world = World::new();
+        world.init_resource::<Failed>();
+        world.insert_resource(Assets::<SplatMaterial>::default());
+        world.add_observer(
+            |failed: bevy::ecs::observer::On<SplatTexturesFailed>, mut log: ResMut<Failed>| {
+                let cancelled = matches!(failed.failure, TextureFailure::Cancelled);
+                log.0.push((failed.target.clone(), cancelled));
+            },
+        );
+        let target = world
+            .resource_mut::<Assets<SplatMaterial>>()
+            .add(SplatMaterial {
+                base: StandardMaterial::default(),
+                extension: SplatExtension::default(),
+            });
+        let task = world
+            .spawn((
+                TextureFailed(TextureFailure::Cancelled),
+                PatchSplatTextures {
+                    target: target.clone(),
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(patch_splat_material_textures);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Failed>().0, [(target.clone(), true)]);
+        assert!(world.get_entity(task).is_err(), "task despawned");
+        let material = world
+            .resource::<Assets<SplatMaterial>>()
+            .get(&target)
+            .unwrap();
+        assert!(material.extension.albedo.is_none(), "left on fallbacks");
+    }
+}
@@ -0,0 +1,174 @@
+// Terrain splat extension for `StandardMaterial`.
+//
+// Blends up to four layers of generated surface texture arrays, weighted by
+// an RGBA splat map or by per-layer height / slope rules, then lights the
+// result through the regular PBR path.  Bindings mirror `SplatExtension`
+// in `splat.rs`.
+
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, calculate_tbn_mikktspace},
+    pbr_types,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+const MAX_SPLAT_LAYERS: u32 = 4u;
+
+struct SplatLayerParams {
+    // Linear RGB multiplier for the layer's albedo; alpha unused.
+    tint: vec4<f32>,
+    // x: roughness factor, y: metallic factor, z: UV scale.
+    pbr: vec4<f32>,
+    // x/y: world-space height range, z: fade width, w: 1 when enabled.
+    height: vec4<f32>,
+    // x/y: slope range (0 flat … 1 vertical), z: fade width, w: 1 when enabled.
+    slope: vec4<f32>,
+}
+
+struct SplatParams {
+    layers: array<SplatLayerParams, 4>,
+    layer_count: u32,
+    use_weight_map: u32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> splat: SplatParams;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var albedo_array: texture_2d_array<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var array_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(103) var normal_array: texture_2d_array<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(104) var orm_array: texture_2d_array<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(105) var weight_map: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(106) var weight_sampler: sampler;
+
+// 1 inside [range.x, range.y], fading to 0 over `range.z` outside it; 1
+// everywhere when the rule is disabled.
+fn band(x: f32, range: vec4<f32>) -> f32 {
+    let fade = max(range.z, 1e-4);
+    let inside = smoothstep(range.x - fade, range.x, x)
+        * (1.0 - smoothstep(range.y, range.y + fade, x));
+    return select(1.0, inside, range.w > 0.5);
+}
+
+// Mesh UVs, or world-space XZ on meshes without `ATTRIBUTE_UV_0`.
+fn splat_uv(in: VertexOutput) -> vec2<f32> {
+#ifdef VERTEX_UVS_A
+    return in.uv;
+#else
+    return in.world_position.xz;
+#endif
+}
+
+// Tangent frame from screen-space derivatives, for meshes without vertex
+// tangents.  The bitangent follows -v so the green channel points "up" in
+// the image, matching the mikktspace convention the generators target.
+fn cotangent_frame(N: vec3<f32>, p: vec3<f32>, uv: vec2<f32>) -> mat3x3<f32> {
+    let dp1 = dpdx(p);
+    let dp2 = dpdy(p);
+    let duv1 = dpdx(uv);
+    let duv2 = dpdy(uv);
+    let dp2perp = cross(dp2, N);
+    let dp1perp = cross(N, dp1);
+    let T = dp2perp * duv1.x + dp1perp * duv2.x;
+    let B = dp2perp * duv1.y + dp1perp * duv2.y;
+    let inv_max = inverseSqrt(max(max(dot(T, T), dot(B, B)), 1e-12));
+    return mat3x3(T * inv_max, -B * inv_max, N);
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    let world_normal = normalize(in.world_normal);
+    let slope = 1.0 - clamp(world_normal.y, 0.0, 1.0);
+    let base_uv = splat_uv(in);
+    let map_weights = textureSample(weight_map, weight_sampler, base_uv);
+
+#ifdef VERTEX_TANGENTS
+    let TBN = calculate_tbn_mikktspace(in.world_normal, in.world_tangent);
+#else
+    let TBN = cotangent_frame(world_normal, in.world_position.xyz, base_uv);
+#endif
+
+    var total = 0.0;
+    var albedo = vec3(0.0);
+    var normal_ts = vec3(0.0);
+    var occlusion = 0.0;
+    var roughness = 0.0;
+    var metallic = 0.0;
+    // Every layer is sampled regardless of its weight: the loop bound is a
+    // uniform, which keeps implicit-derivative sampling in uniform control
+    // flow.
+    for (var i = 0u; i < min(splat.layer_count, MAX_SPLAT_LAYERS); i += 1u) {
+        let layer = splat.layers[i];
+        var w = band(in.world_position.y, layer.height) * band(slope, layer.slope);
+        if splat.use_weight_map != 0u {
+            w = map_weights[i];
+        }
+
+        let uv = base_uv * layer.pbr.z;
+        let a = textureSample(albedo_array, array_sampler, uv, i32(i));
+        let n = textureSample(normal_array, array_sampler, uv, i32(i));
+        let orm = textureSample(orm_array, array_sampler, uv, i32(i));
+
+        total += w;
+        albedo += w * a.rgb * layer.tint.rgb;
+        normal_ts += w * (n.xyz * 2.0 - 1.0);
+        occlusion += w * orm.r;
+        roughness += w * orm.g * layer.pbr.x;
+        metallic += w * orm.b * layer.pbr.y;
+    }
+    // Nothing claimed this fragment: fall back to the first layer's look
+    // rather than rendering black.
+    if total < 1e-5 {
+        let layer = splat.layers[0];
+        let uv = base_uv * layer.pbr.z;
+        total = 1.0;
+        albedo = textureSampleLevel(albedo_array, array_sampler, uv, 0, 0.0).rgb * layer.tint.rgb;
+        normal_ts = vec3(0.0, 0.0, 1.0);
+        let orm = textureSampleLevel(orm_array, array_sampler, uv, 0, 0.0);
+        occlusion = orm.r;
+        roughness = orm.g * layer.pbr.x;
+        metallic = orm.b * layer.pbr.y;
+    }
+    let inv_total = 1.0 / total;
+
+    pbr_input.material.base_color = vec4(albedo * inv_total, pbr_input.material.base_color.a);
+    pbr_input.material.perceptual_roughness = clamp(roughness * inv_total, 0.089, 1.0);
+    pbr_input.material.metallic = clamp(metallic * inv_total, 0.0, 1.0);
+    pbr_input.diffuse_occlusion = vec3(occlusion * inv_total);
+
+    // Flip only where Bevy's `apply_normal_mapping` would: on back faces of
+    // a double-sided material.  The TBN is built from the unflipped mesh
+    // normal, as Bevy's is.
+    let double_sided =
+        (pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_DOUBLE_SIDED_BIT) != 0u;
+    var Nt = normalize(normal_ts);
+    if double_sided && !is_front {
+        Nt = -Nt;
+    }
+    pbr_input.N = normalize(Nt.x * TBN[0] + Nt.y * TBN[1] + Nt.z * TBN[2]);
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+#ifdef PREPASS_PIPELINE
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+    return out;
+}